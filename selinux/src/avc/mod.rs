@@ -1,18 +1,20 @@
 #[cfg(test)]
 mod tests;
 
+use std::cell::Cell;
 use std::convert::TryFrom;
 use std::marker::PhantomData;
 use std::mem::MaybeUninit;
 use std::os::raw::{c_char, c_int, c_uint, c_void};
-use std::sync::Once;
+use std::sync::{Mutex, MutexGuard, Once, PoisonError};
 use std::{io, ptr};
 
+use once_cell::sync::Lazy;
 use reference_counted_singleton::{RCSRef, RefCountedSingleton};
 
 use crate::errors::{Error, Result};
 use crate::utils::{ret_val_to_result, str_to_c_string};
-use crate::SecurityContext;
+use crate::{SecurityClass, SecurityContext};
 
 /// Access vector cache.
 #[derive(Debug, PartialEq, Eq)]
@@ -45,6 +47,9 @@ impl AccessVectorCache {
     /// initialized succeeds only if the subsequent initialization uses the same
     /// set of options as the previous, still in scope, one.
     ///
+    /// This returns an error if called from a call back invoked by
+    /// [`StatusPage::updated`], e.g., a policy reload hook.
+    ///
     /// See: `avc_open()`.
     #[doc(alias = "avc_open")]
     pub fn initialize(options: &[(c_int, *const c_void)]) -> Result<RCSRef<Self>> {
@@ -58,6 +63,13 @@ impl AccessVectorCache {
         options.sort_unstable();
         options.dedup();
 
+        if status_page_users_locked_by_current_thread() {
+            // Another thread might hold the access vector cache singleton lock,
+            // while waiting for the status page users lock held by this thread.
+            let err = io::ErrorKind::WouldBlock.into();
+            return Err(Error::from_io("AccessVectorCache::initialize()", err));
+        }
+
         let count = c_uint::try_from(options.len())?;
         let options_ptr = if count == 0 {
             ptr::null_mut()
@@ -69,12 +81,15 @@ impl AccessVectorCache {
         let avc = get_or_init_access_vector_cache();
 
         let result = avc.get_or_init(|| {
-            if unsafe { selinux_sys::avc_open(options_ptr, count) } == -1_i32 {
-                Err(Error::last_io_error("avc_open()"))
-            } else {
-                newly_initialized = true;
-                Ok(AccessVectorCache(options.clone())) // First initialization succeeded.
-            }
+            with_status_page_users(|users| {
+                if unsafe { selinux_sys::avc_open(options_ptr, count) } == -1_i32 {
+                    Err(Error::last_io_error("avc_open()"))
+                } else {
+                    users.access_vector_cache.set(true);
+                    newly_initialized = true;
+                    Ok(AccessVectorCache(options.clone())) // First initialization succeeded.
+                }
+            })
         });
 
         match result {
@@ -193,11 +208,250 @@ impl AccessVectorCache {
             })
         }
     }
+
+    /// Return whether the policy grants the `requested_access` permissions of
+    /// `target_class` to `source_id` on `target_id`, without auditing
+    /// the decision.
+    ///
+    /// The decision is computed by the kernel on the first request, then
+    /// served from the cache. Access is granted when in permissive mode.
+    ///
+    /// See: `avc_has_perm_noaudit()`.
+    #[doc(alias = "avc_has_perm_noaudit")]
+    pub fn has_permission_no_audit(
+        &self,
+        source_id: &SecurityID,
+        target_id: &SecurityID,
+        target_class: SecurityClass,
+        requested_access: selinux_sys::access_vector_t,
+    ) -> Result<bool> {
+        let mut decision = MaybeUninit::<selinux_sys::av_decision>::uninit();
+        let r = unsafe {
+            selinux_sys::avc_has_perm_noaudit(
+                source_id.security_id,
+                target_id.security_id,
+                target_class.value(),
+                requested_access,
+                ptr::null_mut(),
+                decision.as_mut_ptr(),
+            )
+        };
+
+        if r == -1_i32 {
+            let err = io::Error::last_os_error();
+            if err.raw_os_error() == Some(libc::EACCES) {
+                Ok(false)
+            } else {
+                Err(Error::from_io("avc_has_perm_noaudit()", err))
+            }
+        } else {
+            Ok(true)
+        }
+    }
+
+    /// Populate the cache with the access decisions of the given requests,
+    /// so that later checks of the same requests are served from the cache.
+    ///
+    /// Each request is a `(source_id, target_id, target_class, requested_access)`
+    /// tuple. This returns whether each request is granted, or the first error
+    /// that is not a denial.
+    ///
+    /// See: `avc_has_perm_noaudit()`.
+    #[doc(alias = "avc_has_perm_noaudit")]
+    pub fn warm_up(
+        &self,
+        requests: &[(
+            &SecurityID,
+            &SecurityID,
+            SecurityClass,
+            selinux_sys::access_vector_t,
+        )],
+    ) -> Result<Vec<bool>> {
+        requests
+            .iter()
+            .map(|&(source_id, target_id, target_class, requested_access)| {
+                self.has_permission_no_audit(source_id, target_id, target_class, requested_access)
+            })
+            .collect()
+    }
 }
 
 impl Drop for AccessVectorCache {
     fn drop(&mut self) {
-        unsafe { selinux_sys::avc_destroy() };
+        with_status_page_users(|users| {
+            unsafe { selinux_sys::avc_destroy() };
+            users.access_vector_cache.set(false);
+
+            // Depending on the version of `libselinux`, `avc_destroy()` might or
+            // might not unmap the status page. Leave it mapped only for an open
+            // `StatusPage`.
+            match users.status_page_fallback.get() {
+                None => unsafe { selinux_sys::selinux_status_close() },
+                Some(fallback) => {
+                    unsafe { selinux_sys::selinux_status_open(c_int::from(fallback)) };
+                }
+            }
+        });
+    }
+}
+
+/// Users of the status page, which `libselinux` maps once per process.
+#[derive(Debug, Default)]
+struct StatusPageUsers {
+    /// `true` while the access vector cache is initialized.
+    access_vector_cache: Cell<bool>,
+    /// The `fallback` argument given to open the [`StatusPage`], while it is open.
+    status_page_fallback: Cell<Option<bool>>,
+}
+
+static STATUS_PAGE_USERS: Lazy<Mutex<StatusPageUsers>> = Lazy::new(Mutex::default);
+
+thread_local! {
+    /// Status page users, while their lock is held by this thread.
+    static LOCKED_STATUS_PAGE_USERS: Cell<*const StatusPageUsers> = const { Cell::new(ptr::null()) };
+}
+
+struct StatusPageUsersGuard(MutexGuard<'static, StatusPageUsers>);
+
+impl Drop for StatusPageUsersGuard {
+    fn drop(&mut self) {
+        LOCKED_STATUS_PAGE_USERS.with(|locked| locked.set(ptr::null()));
+    }
+}
+
+fn status_page_users_locked_by_current_thread() -> bool {
+    !LOCKED_STATUS_PAGE_USERS.with(Cell::get).is_null()
+}
+
+/// Call `f` with the status page users, while holding their lock.
+///
+/// The lock is reentrant, because `selinux_status_updated()` runs call backs
+/// while this thread holds the lock, and these call backs might destroy the
+/// access vector cache.
+fn with_status_page_users<R>(f: impl FnOnce(&StatusPageUsers) -> R) -> R {
+    let locked = LOCKED_STATUS_PAGE_USERS.with(Cell::get);
+    if let Some(users) = unsafe { locked.as_ref() } {
+        return f(users);
+    }
+
+    // The protected data remains consistent even if a holder panicked.
+    let guard = StatusPageUsersGuard(
+        STATUS_PAGE_USERS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner),
+    );
+    LOCKED_STATUS_PAGE_USERS.with(|locked| locked.set(&*guard.0));
+    f(&guard.0)
+}
+
+/// SELinux kernel status page, mapped in memory.
+///
+/// `libselinux` maps the status page once per process. Since `libselinux`
+/// version `3.2`, the [`AccessVectorCache`] also uses that mapping to track
+/// policy reloads. The mapping is therefore shared: it remains mapped while
+/// either a [`StatusPage`] is open or the [`AccessVectorCache`] is initialized,
+/// and dropping one of them does not affect the other.
+#[derive(Debug, PartialEq, Eq)]
+pub struct StatusPage {
+    uses_fallback: bool,
+}
+
+static STATUS_PAGE: Lazy<RefCountedSingleton<StatusPage>> = Lazy::new(RefCountedSingleton::default);
+
+impl StatusPage {
+    /// Map the kernel status page in memory.
+    ///
+    /// If `fallback` is `true` and the kernel does not provide a status page,
+    /// then status changes are tracked through netlink messages instead.
+    ///
+    /// Attempting to open the status page while it is still open returns
+    /// the already opened one.
+    ///
+    /// See: `selinux_status_open()`.
+    #[doc(alias = "selinux_status_open")]
+    pub fn open(fallback: bool) -> Result<RCSRef<'static, Self>> {
+        let result = STATUS_PAGE.get_or_init(|| {
+            with_status_page_users(|users| {
+                let uses_fallback =
+                    match unsafe { selinux_sys::selinux_status_open(c_int::from(fallback)) } {
+                        -1_i32 => return Err(Error::last_io_error("selinux_status_open()")),
+                        0_i32 => false,
+                        _ => true,
+                    };
+
+                users.status_page_fallback.set(Some(fallback));
+                Ok(StatusPage { uses_fallback })
+            })
+        });
+
+        match result {
+            Ok(value) => Ok(value),
+
+            Err(None) => Err(Error::LockPoisoned {
+                operation: "RefCountedSingleton::get_or_init()",
+            }),
+
+            Err(Some(err)) => Err(err),
+        }
+    }
+
+    /// Return `true` if status changes are tracked through netlink messages,
+    /// because the kernel does not provide a status page.
+    #[must_use]
+    pub fn uses_fallback(&self) -> bool {
+        self.uses_fallback
+    }
+
+    /// Return `true` if the enforcing mode or the loaded policy changed since
+    /// the last call to this function.
+    ///
+    /// This invokes the [`crate::call_back::EnforcingChange`] and
+    /// [`crate::call_back::SecurityPolicyReload`] call backs on changes.
+    /// These call backs may drop the [`AccessVectorCache`], but initializing
+    /// it from them returns an error.
+    ///
+    /// See: `selinux_status_updated()`.
+    #[doc(alias = "selinux_status_updated")]
+    pub fn updated(&self) -> Result<bool> {
+        with_status_page_users(
+            |_users| match unsafe { selinux_sys::selinux_status_updated() } {
+                -1_i32 => Err(Error::last_io_error("selinux_status_updated()")),
+                0_i32 => Ok(false),
+                _ => Ok(true),
+            },
+        )
+    }
+
+    /// Return `true` if the loaded policy denies undefined object classes
+    /// and permissions.
+    ///
+    /// This reads the status page instead of querying the kernel, so it is
+    /// cheap. ⚠️ The returned value may be stale if the policy is reloaded
+    /// concurrently. Use [`crate::undefined_handling`] to query the kernel.
+    ///
+    /// See: `selinux_status_deny_unknown()`.
+    #[doc(alias = "selinux_status_deny_unknown")]
+    pub fn denies_unknown(&self) -> Result<bool> {
+        with_status_page_users(|_users| {
+            match unsafe { selinux_sys::selinux_status_deny_unknown() } {
+                -1_i32 => Err(Error::last_io_error("selinux_status_deny_unknown()")),
+                0_i32 => Ok(false),
+                _ => Ok(true),
+            }
+        })
+    }
+}
+
+impl Drop for StatusPage {
+    fn drop(&mut self) {
+        with_status_page_users(|users| {
+            users.status_page_fallback.set(None);
+
+            // The access vector cache unmaps the status page when it is destroyed.
+            if !users.access_vector_cache.get() {
+                unsafe { selinux_sys::selinux_status_close() };
+            }
+        });
     }
 }
 
@@ -127,3 +127,81 @@ fn access_vector_cache_kernel_initial_security_id() {
         }
     }
 }
+
+#[serial]
+#[test]
+fn access_vector_cache_has_permission_no_audit() {
+    let options = &[(selinux_sys::AVC_OPT_SETENFORCE, ptr::null())];
+    let avc = super::AccessVectorCache::initialize(options).unwrap();
+
+    let context = crate::SecurityContext::current(false).unwrap();
+    let sid = avc.security_id_from_security_context(context).unwrap();
+    let class = crate::SecurityClass::from_name("process").unwrap();
+    let fork = class.access_vector_bit("fork").unwrap();
+
+    let _granted = avc
+        .has_permission_no_audit(&sid, &sid, class, fork)
+        .unwrap();
+
+    let decisions = avc.warm_up(&[(&sid, &sid, class, fork)]).unwrap();
+    assert_eq!(decisions.len(), 1);
+
+    assert!(avc.warm_up(&[]).unwrap().is_empty());
+}
+
+#[serial]
+#[test]
+fn status_page() {
+    let status_page = super::StatusPage::open(true).unwrap();
+    let _ignored = format!("{:?}", &status_page);
+    let _uses_fallback = status_page.uses_fallback();
+
+    let status_page2 = super::StatusPage::open(false).unwrap();
+    assert_eq!(status_page, status_page2);
+
+    let _updated = status_page.updated().unwrap();
+
+    let denies_unknown = status_page.denies_unknown().unwrap();
+    match crate::undefined_handling().unwrap() {
+        crate::UndefinedHandling::Allowed => assert!(!denies_unknown),
+        crate::UndefinedHandling::DeniedAtRunTime => assert!(denies_unknown),
+        _ => {}
+    }
+}
+
+#[serial]
+#[test]
+fn status_page_with_access_vector_cache() {
+    let avc = super::AccessVectorCache::initialize(&[]).unwrap();
+    let status_page = super::StatusPage::open(false).unwrap();
+
+    // Destroying the access vector cache keeps the status page mapped.
+    drop(avc);
+    let _updated = status_page.updated().unwrap();
+
+    // Dropping the status page keeps it mapped for the access vector cache.
+    let avc = super::AccessVectorCache::initialize(&[]).unwrap();
+    drop(status_page);
+    assert_ne!(unsafe { selinux_sys::selinux_status_updated() }, -1_i32);
+    drop(avc);
+}
+
+#[serial]
+#[test]
+fn status_page_call_back_reentrancy() {
+    let avc = super::AccessVectorCache::initialize(&[]).unwrap();
+    let status_page = super::StatusPage::open(false).unwrap();
+
+    // Call backs invoked by `StatusPage::updated()` run with the status page
+    // users locked by the current thread.
+    super::with_status_page_users(|_users| {
+        let err = super::AccessVectorCache::initialize(&[]).unwrap_err();
+        assert_matches!(err, crate::errors::Error::IO { .. });
+
+        let _status_page = super::StatusPage::open(false).unwrap();
+        drop(avc);
+    });
+
+    let _updated = status_page.updated().unwrap();
+    drop(super::AccessVectorCache::initialize(&[]).unwrap());
+}
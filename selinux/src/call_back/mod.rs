@@ -2,6 +2,12 @@
 mod tests;
 
 use std::os::raw::{c_char, c_int, c_void};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex};
+
+use once_cell::sync::Lazy;
+
+use crate::errors::{Error, Result};
 
 /// Call back for SELinux operations.
 pub trait CallBack {
@@ -118,6 +124,109 @@ impl CallBack for SecurityPolicyReload {
     }
 }
 
+/// Maximum number of policy reload hooks that can be registered at the same time.
+pub const MAX_POLICY_RELOAD_HOOKS: usize = 64;
+
+type PolicyReloadHookFn = Arc<dyn Fn(u32) + Send + Sync>;
+
+#[derive(Default)]
+struct PolicyReloadHooks {
+    next_id: u64,
+    hooks: Vec<(u64, PolicyReloadHookFn)>,
+    previous_call_back: Option<<SecurityPolicyReload as CallBack>::CallBackType>,
+}
+
+static POLICY_RELOAD_HOOKS: Lazy<Mutex<PolicyReloadHooks>> = Lazy::new(Mutex::default);
+
+/// Register a hook to be called, with the new policy sequence number, each
+/// time the system security policy is reloaded.
+///
+/// While at least one hook is registered, the [`SecurityPolicyReload`] call
+/// back is replaced by a dispatcher calling all hooks. The previous call back
+/// is restored when the last hook is unregistered. The dispatcher calls the
+/// previous call back after all hooks, and returns its result.
+/// Hooks are called when `libselinux` processes kernel notifications, e.g.,
+/// through [`crate::avc::StatusPage::updated`].
+///
+/// Hooks are called without holding the hooks registry lock, so a hook may
+/// register or unregister hooks. A panic raised by a hook is caught and
+/// ignored, so that it does not unwind into `libselinux`.
+///
+/// The hook is unregistered when the returned handle is dropped.
+/// At most [`MAX_POLICY_RELOAD_HOOKS`] hooks can be registered at the same time.
+///
+/// See: `selinux_set_callback()`.
+#[doc(alias = "selinux_set_callback")]
+pub fn register_policy_reload_hook<F>(hook: F) -> Result<PolicyReloadHookHandle>
+where
+    F: Fn(u32) + Send + Sync + 'static,
+{
+    let mut hooks = POLICY_RELOAD_HOOKS
+        .lock()
+        .map_err(|_r| Error::LockPoisoned {
+            operation: "register_policy_reload_hook()",
+        })?;
+
+    if hooks.hooks.len() >= MAX_POLICY_RELOAD_HOOKS {
+        return Err(Error::TooManyPolicyReloadHooks {
+            limit: MAX_POLICY_RELOAD_HOOKS,
+        });
+    }
+
+    if hooks.hooks.is_empty() {
+        hooks.previous_call_back = SecurityPolicyReload::get_call_back();
+        SecurityPolicyReload::set_call_back(Some(dispatch_policy_reload));
+    }
+
+    let id = hooks.next_id;
+    hooks.next_id = hooks.next_id.wrapping_add(1);
+    hooks.hooks.push((id, Arc::new(hook)));
+    Ok(PolicyReloadHookHandle { id })
+}
+
+unsafe extern "C" fn dispatch_policy_reload(seqno: c_int) -> c_int {
+    // Copy the hooks, so that they run without holding the lock.
+    let (hooks, previous_call_back) = match POLICY_RELOAD_HOOKS.lock() {
+        Ok(hooks) => {
+            let list: Vec<PolicyReloadHookFn> = hooks
+                .hooks
+                .iter()
+                .map(|(_id, hook)| Arc::clone(hook))
+                .collect();
+            (list, hooks.previous_call_back)
+        }
+
+        Err(_r) => return 0_i32,
+    };
+
+    let hook_seqno = seqno as u32;
+    for hook in hooks {
+        let _ignored = panic::catch_unwind(AssertUnwindSafe(|| hook(hook_seqno)));
+    }
+
+    previous_call_back.map_or(0_i32, |call_back| call_back(seqno))
+}
+
+/// Registered policy reload hook.
+///
+/// Dropping this instance unregisters the hook.
+#[derive(Debug)]
+pub struct PolicyReloadHookHandle {
+    id: u64,
+}
+
+impl Drop for PolicyReloadHookHandle {
+    fn drop(&mut self) {
+        if let Ok(mut hooks) = POLICY_RELOAD_HOOKS.lock() {
+            hooks.hooks.retain(|&(id, _)| id != self.id);
+
+            if hooks.hooks.is_empty() {
+                SecurityPolicyReload::set_call_back(hooks.previous_call_back.take());
+            }
+        }
+    }
+}
+
 /// Log type argument indicating the type of message.
 pub mod log_type {
     use std::os::raw::c_int;
@@ -46,11 +46,92 @@ fn enforcing_change() {
     template::<super::EnforcingChange>(enforcing_change_call_back);
 }
 
+#[serial_test::serial]
 #[test]
 fn security_policy_reload() {
     template::<super::SecurityPolicyReload>(security_policy_reload_call_back);
 }
 
+#[serial_test::serial]
+#[test]
+fn register_policy_reload_hook() {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    use super::CallBack;
+
+    let old_call_back = super::SecurityPolicyReload::get_call_back();
+    super::SecurityPolicyReload::set_call_back(None);
+
+    let seqno = Arc::new(AtomicU32::new(0));
+    let hook_seqno = Arc::clone(&seqno);
+    let hook =
+        super::register_policy_reload_hook(move |n| hook_seqno.store(n, Ordering::SeqCst)).unwrap();
+    let _ignored = format!("{:?}", &hook);
+
+    let dispatcher = super::SecurityPolicyReload::get_call_back().unwrap();
+    assert_eq!(unsafe { dispatcher(42) }, 0_i32);
+    assert_eq!(seqno.load(Ordering::SeqCst), 42);
+
+    let hooks: Result<Vec<_>, _> = (1..super::MAX_POLICY_RELOAD_HOOKS)
+        .map(|_| super::register_policy_reload_hook(|_| {}))
+        .collect();
+    let hooks = hooks.unwrap();
+    let err = super::register_policy_reload_hook(|_| {}).unwrap_err();
+    assert!(matches!(
+        err,
+        crate::errors::Error::TooManyPolicyReloadHooks { .. }
+    ));
+    drop(hooks);
+
+    drop(hook);
+    assert!(super::SecurityPolicyReload::get_call_back().is_none());
+
+    super::SecurityPolicyReload::set_call_back(old_call_back);
+}
+
+#[serial_test::serial]
+#[test]
+fn dispatch_policy_reload() {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use super::CallBack;
+
+    static PREVIOUS_SEQNO: AtomicU32 = AtomicU32::new(0);
+
+    unsafe extern "C" fn previous_call_back(seqno: c_int) -> c_int {
+        PREVIOUS_SEQNO.store(seqno as u32, Ordering::SeqCst);
+        7_i32
+    }
+
+    let old_call_back = super::SecurityPolicyReload::get_call_back();
+    super::SecurityPolicyReload::set_call_back(Some(previous_call_back));
+
+    let panicking_hook = super::register_policy_reload_hook(|_| panic!("hook panic")).unwrap();
+
+    // A hook unregistering itself must not dead-lock.
+    let self_handle = Arc::new(Mutex::new(None));
+    let hook_handle = Arc::clone(&self_handle);
+    let handle = super::register_policy_reload_hook(move |_| {
+        drop(hook_handle.lock().unwrap().take());
+    })
+    .unwrap();
+    *self_handle.lock().unwrap() = Some(handle);
+
+    let dispatcher = super::SecurityPolicyReload::get_call_back().unwrap();
+    assert_eq!(unsafe { dispatcher(42) }, 7_i32);
+    assert_eq!(PREVIOUS_SEQNO.load(Ordering::SeqCst), 42);
+    assert!(self_handle.lock().unwrap().is_none());
+
+    drop(panicking_hook);
+    let restored = super::SecurityPolicyReload::get_call_back().unwrap();
+    assert_eq!(unsafe { restored(43) }, 7_i32);
+    assert_eq!(PREVIOUS_SEQNO.load(Ordering::SeqCst), 43);
+
+    super::SecurityPolicyReload::set_call_back(old_call_back);
+}
+
 // Dummy call back functions, of the correct prototypes.
 
 unsafe extern "C" fn audit_call_back(
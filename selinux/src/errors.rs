@@ -81,6 +81,22 @@ pub enum Error {
     /// Integer is out of valid range.
     #[error(transparent)]
     IntegerOutOfRange(#[from] TryFromIntError),
+
+    /// Too many policy reload hooks are registered.
+    #[error("Cannot register more than {limit} policy reload hooks")]
+    TooManyPolicyReloadHooks {
+        /// Maximum number of hooks registered at the same time.
+        limit: usize,
+    },
+
+    /// Some booleans could not be restored.
+    #[error("{} boolean(s) could not be restored", .failed.len())]
+    PartialRestore {
+        /// Names of the restored booleans.
+        succeeded: Vec<String>,
+        /// Names of the booleans that could not be restored, with the causes.
+        failed: Vec<(String, Error)>,
+    },
 }
 
 impl Error {
@@ -141,7 +157,6 @@ impl Error {
         Self::set_errno(0);
     }
 
-    #[allow(dead_code)] // This is used by unit tests.
     pub(crate) fn io_source(&self) -> Option<&io::Error> {
         match self {
             Self::IO { source, .. } => Some(source),
@@ -1,12 +1,15 @@
 #[cfg(test)]
 mod tests;
 
+use std::cell::RefCell;
 use std::ffi::{CStr, CString};
 use std::hash::Hash;
 use std::marker::PhantomData;
 use std::os::raw::{c_char, c_int, c_void};
+use std::os::unix::io::AsRawFd;
 use std::path::Path;
-use std::{cmp, io, iter, ptr, slice};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::{cmp, fs, io, iter, ptr, slice};
 
 use crate::errors::{Error, Result};
 use crate::utils::*;
@@ -76,6 +79,42 @@ impl<T: BackEnd> Labeler<T> {
             .ok_or_else(|| Error::last_io_error("selabel_open()"))
     }
 
+    /// Initialize a labeling handle to be used for lookup operations, reading
+    /// the specification file from an open file descriptor.
+    ///
+    /// The specification file is passed to libselinux as the
+    /// `/proc/self/fd/<fd>` path, in a [`SELABEL_OPT_PATH`] option appended to
+    /// `options`. This requires the `/proc` file system to be mounted.
+    /// The file is read while the handle is initialized, so it does not need to
+    /// remain open afterwards.
+    ///
+    /// See: `selabel_open()`.
+    ///
+    /// [`SELABEL_OPT_PATH`]: selinux_sys::SELABEL_OPT_PATH
+    #[doc(alias = "selabel_open")]
+    pub fn from_spec_file<F>(
+        spec_file: &F,
+        options: &[(c_int, *const c_void)],
+        raw_format: bool,
+    ) -> Result<Self>
+    where
+        F: AsRawFd,
+    {
+        let path = format!("/proc/self/fd/{}", spec_file.as_raw_fd());
+        let c_path = str_to_c_string(&path)?;
+
+        let options: Vec<(c_int, *const c_void)> = options
+            .iter()
+            .copied()
+            .chain(iter::once((
+                selinux_sys::SELABEL_OPT_PATH,
+                c_path.as_ptr().cast(),
+            )))
+            .collect();
+
+        Self::new(&options, raw_format)
+    }
+
     /// Obtain SELinux security context from a string label.
     ///
     /// See: `selabel_lookup()`.
@@ -189,6 +228,14 @@ impl Labeler<back_end::File> {
         path: impl AsRef<Path>,
         mode: Option<FileAccessMode>,
     ) -> Result<SecurityContext> {
+        self.look_up_owned_by_path(path.as_ref(), mode)
+    }
+
+    fn look_up_owned_by_path(
+        &self,
+        path: &Path,
+        mode: Option<FileAccessMode>,
+    ) -> Result<SecurityContext<'static>> {
         let (proc, proc_name): (unsafe extern "C" fn(_, _, _, _) -> _, _) = if self.is_raw {
             (selinux_sys::selabel_lookup_raw, "selabel_lookup_raw()")
         } else {
@@ -197,12 +244,179 @@ impl Labeler<back_end::File> {
 
         let handle = self.pointer.as_ptr();
         let mut context: *mut c_char = ptr::null_mut();
-        let c_path = os_str_to_c_string(path.as_ref().as_os_str())?;
+        let c_path = os_str_to_c_string(path.as_os_str())?;
         let mode = mode.map_or(0, FileAccessMode::mode) as c_int;
         let r = unsafe { proc(handle, &mut context, c_path.as_ptr(), mode) };
         SecurityContext::from_result(proc_name, r, context, self.is_raw)
     }
 
+    /// Obtain SELinux security contexts from multiple paths.
+    ///
+    /// The returned list has one result per path, in the same order.
+    ///
+    /// See: `selabel_lookup()`.
+    #[doc(alias = "selabel_lookup")]
+    pub fn look_up_by_paths<P>(
+        &self,
+        paths: &[(P, Option<FileAccessMode>)],
+    ) -> Vec<Result<SecurityContext<'static>>>
+    where
+        P: AsRef<Path>,
+    {
+        paths
+            .iter()
+            .map(|(path, mode)| self.look_up_owned_by_path(path.as_ref(), *mode))
+            .collect()
+    }
+
+    /// Obtain SELinux security context from a path, or return `default` if no
+    /// specification matches the path.
+    ///
+    /// See: `selabel_lookup()`.
+    #[doc(alias = "selabel_lookup")]
+    pub fn look_up_by_path_or<'context>(
+        &'context self,
+        path: impl AsRef<Path>,
+        mode: Option<FileAccessMode>,
+        default: SecurityContext<'context>,
+    ) -> Result<SecurityContext<'context>> {
+        match self.look_up_by_path(path, mode) {
+            Err(err) if err.io_source().and_then(io::Error::raw_os_error) == Some(libc::ENOENT) => {
+                Ok(default)
+            }
+
+            r => r,
+        }
+    }
+
+    /// Obtain SELinux security context from a path, or return `None` if the
+    /// path is under any of the `exclusions` directories, e.g., `/proc`.
+    ///
+    /// Exclusions are matched by path components: `/proc` excludes `/proc`
+    /// and `/proc/self`, but not `/processes`. Paths are not canonicalized.
+    ///
+    /// See: `selabel_lookup()`.
+    #[doc(alias = "selabel_lookup")]
+    pub fn look_up_by_path_excluding<P>(
+        &self,
+        path: impl AsRef<Path>,
+        mode: Option<FileAccessMode>,
+        exclusions: &[P],
+    ) -> Result<Option<SecurityContext<'static>>>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        if exclusions
+            .iter()
+            .any(|exclusion| path.starts_with(exclusion))
+        {
+            Ok(None)
+        } else {
+            self.look_up_owned_by_path(path, mode).map(Some)
+        }
+    }
+
+    /// Compare the security context of each file system object under `root`,
+    /// including `root`, to the one expected by the specifications, as
+    /// `restorecon -n -r` does.
+    ///
+    /// `on_mismatch` is called with the path, the current context and
+    /// the expected context of each file system object whose contexts differ.
+    /// File system objects without a matching specification, or removed
+    /// during the walk, are skipped.
+    /// If `cross_mounts` is `false`, then directories on other file systems
+    /// than `root` are skipped, without being descended.
+    /// Symbolic links are never followed.
+    ///
+    /// See: `selabel_lookup()`, `lgetfilecon()`.
+    #[doc(alias = "selabel_lookup")]
+    #[doc(alias = "lgetfilecon")]
+    pub fn verify_tree<F>(
+        &self,
+        root: impl AsRef<Path>,
+        cross_mounts: bool,
+        mut on_mismatch: F,
+    ) -> Result<TreeVerificationSummary>
+    where
+        F: FnMut(&Path, Option<&SecurityContext>, &SecurityContext),
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        let root = root.as_ref();
+        let is_not_found =
+            |err: &Error| err.io_source().and_then(io::Error::raw_os_error) == Some(libc::ENOENT);
+
+        let root_device = fs::symlink_metadata(root)
+            .map_err(|err| Error::from_io_path("fs::symlink_metadata()", root, err))?
+            .dev();
+        let mut summary = TreeVerificationSummary::default();
+        let mut pending = vec![root.to_path_buf()];
+
+        while let Some(path) = pending.pop() {
+            let metadata = match fs::symlink_metadata(&path) {
+                Ok(metadata) => metadata,
+
+                // The file system object was removed since its directory was read.
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                    summary.skipped += 1;
+                    continue;
+                }
+
+                Err(err) => return Err(Error::from_io_path("fs::symlink_metadata()", &path, err)),
+            };
+
+            if metadata.is_dir() && !cross_mounts && metadata.dev() != root_device {
+                summary.skipped += 1;
+                continue;
+            }
+
+            let mode = FileAccessMode::new(metadata.mode());
+            let expected = match self.look_up_owned_by_path(&path, mode) {
+                Ok(context) => Some(context),
+                Err(err) if is_not_found(&err) => None,
+                Err(err) => return Err(err),
+            };
+
+            if let Some(expected) = expected {
+                let current = match SecurityContext::of_path(&path, false, self.is_raw) {
+                    Ok(current) => current,
+
+                    Err(err) if is_not_found(&err) => {
+                        summary.skipped += 1;
+                        continue;
+                    }
+
+                    Err(err) => return Err(err),
+                };
+
+                if current.as_ref() == Some(&expected) {
+                    summary.correct += 1;
+                } else {
+                    summary.incorrect += 1;
+                    on_mismatch(&path, current.as_ref(), &expected);
+                }
+            } else {
+                summary.skipped += 1;
+            }
+
+            if metadata.is_dir() {
+                let to_error = |err| Error::from_io_path("fs::read_dir()", &path, err);
+                let entries = match fs::read_dir(&path) {
+                    Ok(entries) => entries,
+                    Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                    Err(err) => return Err(to_error(err)),
+                };
+
+                for entry in entries {
+                    pending.push(entry.map_err(to_error)?.path());
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
     /// Obtain a best match SELinux security context.
     ///
     /// See: `selabel_lookup_best_match()`.
@@ -313,6 +527,163 @@ impl Labeler<back_end::File> {
     }
 }
 
+/// Counts of file system objects examined by [`Labeler::verify_tree`].
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct TreeVerificationSummary {
+    /// Number of file system objects having the expected security context.
+    pub correct: u64,
+    /// Number of file system objects not having the expected security context.
+    pub incorrect: u64,
+    /// Number of file system objects without a matching specification,
+    /// or whose directories were not descended.
+    pub skipped: u64,
+}
+
+/// Generation of the default file labelers, incremented by
+/// [`reset_default_file_labeler`].
+static DEFAULT_FILE_LABELERS_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// Default file labelers of this thread, and the generation they belong to.
+    static DEFAULT_FILE_LABELERS: RefCell<(u64, [Option<Labeler<back_end::File>>; 2])> =
+        const { RefCell::new((0, [None, None])) };
+}
+
+/// Obtain SELinux security context of a path from the default file contexts.
+///
+/// The labeling handle is opened on the first call in each thread, then reused
+/// by later calls in the same thread. Each thread therefore holds its own copy
+/// of the file contexts specifications in memory.
+/// Call [`reset_default_file_labeler`] to reopen the handles of all threads
+/// after a policy change.
+///
+/// See: `selabel_open()`, `selabel_lookup()`.
+#[doc(alias = "selabel_open")]
+#[doc(alias = "selabel_lookup")]
+pub fn look_up_by_path_with_default_labeler(
+    path: impl AsRef<Path>,
+    mode: Option<FileAccessMode>,
+    raw_format: bool,
+) -> Result<SecurityContext<'static>> {
+    let generation = DEFAULT_FILE_LABELERS_GENERATION.load(Ordering::Relaxed);
+
+    DEFAULT_FILE_LABELERS.with(|labelers| {
+        let (labelers_generation, labelers) = &mut *labelers.borrow_mut();
+        if *labelers_generation != generation {
+            // The handles were opened before the last reset.
+            *labelers = [None, None];
+            *labelers_generation = generation;
+        }
+
+        let labeler = &mut labelers[usize::from(raw_format)];
+
+        let labeler = if let Some(labeler) = labeler {
+            labeler
+        } else {
+            labeler.insert(Labeler::new(&[], raw_format)?)
+        };
+
+        labeler.look_up_owned_by_path(path.as_ref(), mode)
+    })
+}
+
+/// Invalidate the labeling handles used by
+/// [`look_up_by_path_with_default_labeler`] in all threads, so that the next
+/// look up in each thread reopens them.
+///
+/// The handles of the current thread are closed immediately. Other threads
+/// close theirs on their next look up.
+///
+/// See: `selabel_close()`.
+#[doc(alias = "selabel_close")]
+pub fn reset_default_file_labeler() {
+    DEFAULT_FILE_LABELERS_GENERATION.fetch_add(1, Ordering::Relaxed);
+    DEFAULT_FILE_LABELERS.with(|labelers| labelers.borrow_mut().1 = [None, None]);
+}
+
+impl Labeler<back_end::DB> {
+    /// Obtain SELinux security context of a database.
+    ///
+    /// Databases, schemas, tables and columns are distinct object classes,
+    /// which may be assigned different contexts for the same name.
+    ///
+    /// See: `selabel_lookup()`.
+    #[doc(alias = "selabel_lookup")]
+    pub fn look_up_database(&self, database: &str) -> Result<SecurityContext<'_>> {
+        let c_name = str_to_c_string(database)?;
+        self.look_up(&c_name, selinux_sys::SELABEL_DB_DATABASE)
+    }
+
+    /// Obtain SELinux security context of a database schema.
+    ///
+    /// Databases, schemas, tables and columns are distinct object classes,
+    /// which may be assigned different contexts for the same name.
+    ///
+    /// See: `selabel_lookup()`.
+    #[doc(alias = "selabel_lookup")]
+    pub fn look_up_schema(&self, schema: &str) -> Result<SecurityContext<'_>> {
+        let c_name = str_to_c_string(schema)?;
+        self.look_up(&c_name, selinux_sys::SELABEL_DB_SCHEMA)
+    }
+
+    /// Obtain SELinux security context of a database column.
+    ///
+    /// The column is looked up by the name returned by
+    /// [`database_object_name`].
+    ///
+    /// See: `selabel_lookup()`.
+    #[doc(alias = "selabel_lookup")]
+    pub fn look_up_column(
+        &self,
+        schema: Option<&str>,
+        table: &str,
+        column: &str,
+    ) -> Result<SecurityContext<'_>> {
+        let name = database_object_name(schema, table, Some(column));
+        let c_name = str_to_c_string(&name)?;
+        self.look_up(&c_name, selinux_sys::SELABEL_DB_COLUMN)
+    }
+}
+
+/// Return the name of a database table, or of a column of that table,
+/// in the `schema.table.column` format used as a key by the database objects
+/// contexts backend.
+///
+/// The `schema` and `column` components are omitted when they are `None`.
+#[must_use]
+pub fn database_object_name(schema: Option<&str>, table: &str, column: Option<&str>) -> String {
+    schema
+        .into_iter()
+        .chain(iter::once(table))
+        .chain(column)
+        .collect::<Vec<&str>>()
+        .join(".")
+}
+
+/// Escape all regular expression metacharacters of `path`, so that it can be
+/// used literally in a file contexts specification.
+#[must_use]
+pub fn regex_escape_path(path: &str) -> String {
+    let mut escaped = String::with_capacity(path.len());
+    for character in path.chars() {
+        if "\\^$.|?*+()[]{}".contains(character) {
+            escaped.push('\\');
+        }
+        escaped.push(character);
+    }
+    escaped
+}
+
+/// Return a file contexts specification pattern matching `path` and all paths
+/// under it, e.g., `/srv/www\.example(/.*)?` for `/srv/www.example`.
+///
+/// The pattern has no `^` and `$` anchors, as is customary in file contexts
+/// specifications: `libselinux` anchors patterns when compiling them.
+#[must_use]
+pub fn regex_anchor_path(path: &str) -> String {
+    format!("{}(/.*)?", regex_escape_path(path.trim_end_matches('/')))
+}
+
 /// Digest of spec files and list of files used.
 ///
 /// ⚠️ This instance does **NOT** own the `digest` or the `spec_files`.
@@ -5,6 +5,7 @@ use std::ffi::CStr;
 use std::os::raw::c_void;
 use std::path::Path;
 use std::ptr;
+use std::sync::atomic::Ordering;
 
 #[test]
 fn labeler_new_file() {
@@ -56,6 +57,177 @@ fn labeler_look_up_by_path() {
     }
 }
 
+#[test]
+fn labeler_look_up_by_paths() {
+    for &raw_format in &[false, true] {
+        let labeler = super::Labeler::<super::back_end::File>::new(&[], raw_format).unwrap();
+        assert!(labeler.look_up_by_paths::<&str>(&[]).is_empty());
+
+        let contexts = labeler.look_up_by_paths(&[("/lib", None), ("/usr/lib", None)]);
+        assert_eq!(contexts.len(), 2);
+        for (context, path) in contexts.iter().zip(&["/lib", "/usr/lib"]) {
+            let expected = labeler.look_up_by_path(path, None).unwrap();
+            assert_eq!(context.as_ref().unwrap().as_bytes(), expected.as_bytes());
+        }
+    }
+}
+
+#[test]
+fn labeler_look_up_by_path_or() {
+    use std::io::Write;
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    writeln!(file, "/srv(/.*)? system_u:object_r:var_t:s0").unwrap();
+    file.flush().unwrap();
+
+    let c_path = std::ffi::CString::new(file.path().as_os_str().as_bytes()).unwrap();
+    let options = &[(selinux_sys::SELABEL_OPT_PATH, c_path.as_ptr().cast())];
+    let labeler = super::Labeler::<super::back_end::File>::new(options, true).unwrap();
+
+    let default = unsafe { CStr::from_ptr("system_u:object_r:default_t:s0\0".as_ptr().cast()) };
+
+    let context = crate::SecurityContext::from_c_str(default, true);
+    let context = labeler
+        .look_up_by_path_or("/srv/www", None, context)
+        .unwrap();
+    assert_eq!(context.as_bytes(), b"system_u:object_r:var_t:s0");
+
+    let context = crate::SecurityContext::from_c_str(default, true);
+    let context = labeler.look_up_by_path_or("/etc", None, context).unwrap();
+    assert_eq!(context.as_bytes(), default.to_bytes());
+}
+
+#[test]
+fn labeler_look_up_by_path_excluding() {
+    use std::io::Write;
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    writeln!(file, "/srv(/.*)? system_u:object_r:var_t:s0").unwrap();
+    writeln!(file, "/proc(/.*)? system_u:object_r:proc_t:s0").unwrap();
+    file.flush().unwrap();
+
+    let c_path = std::ffi::CString::new(file.path().as_os_str().as_bytes()).unwrap();
+    let options = &[(selinux_sys::SELABEL_OPT_PATH, c_path.as_ptr().cast())];
+    let labeler = super::Labeler::<super::back_end::File>::new(options, true).unwrap();
+
+    let exclusions = &["/proc", "/sys"];
+    let context = labeler
+        .look_up_by_path_excluding("/srv/www", None, exclusions)
+        .unwrap()
+        .unwrap();
+    assert_eq!(context.as_bytes(), b"system_u:object_r:var_t:s0");
+
+    for &path in &["/proc", "/proc/self"] {
+        assert!(labeler
+            .look_up_by_path_excluding(path, None, exclusions)
+            .unwrap()
+            .is_none());
+    }
+
+    let no_exclusions: &[&str] = &[];
+    assert!(labeler
+        .look_up_by_path_excluding("/proc/self", None, no_exclusions)
+        .unwrap()
+        .is_some());
+
+    labeler
+        .look_up_by_path_excluding("/etc", None, exclusions)
+        .unwrap_err();
+}
+
+#[test]
+fn look_up_by_path_with_default_labeler() {
+    for &raw_format in &[false, true] {
+        let context1 =
+            super::look_up_by_path_with_default_labeler("/lib", None, raw_format).unwrap();
+        let context2 =
+            super::look_up_by_path_with_default_labeler("/lib", None, raw_format).unwrap();
+        assert_eq!(context1.as_bytes(), context2.as_bytes());
+        assert_eq!(context1.is_raw_format(), raw_format);
+
+        super::reset_default_file_labeler();
+
+        let context3 =
+            super::look_up_by_path_with_default_labeler("/lib", None, raw_format).unwrap();
+        assert_eq!(context1.as_bytes(), context3.as_bytes());
+
+        // A reset in another thread invalidates the handles of this thread.
+        std::thread::spawn(super::reset_default_file_labeler)
+            .join()
+            .unwrap();
+        let generation = super::DEFAULT_FILE_LABELERS_GENERATION.load(Ordering::Relaxed);
+        super::DEFAULT_FILE_LABELERS.with(|labelers| assert_ne!(labelers.borrow().0, generation));
+
+        let context4 =
+            super::look_up_by_path_with_default_labeler("/lib", None, raw_format).unwrap();
+        assert_eq!(context1.as_bytes(), context4.as_bytes());
+        super::DEFAULT_FILE_LABELERS.with(|labelers| assert_eq!(labelers.borrow().0, generation));
+    }
+}
+
+#[test]
+fn labeler_verify_tree() {
+    let labeler = super::Labeler::<super::back_end::File>::new(&[], false).unwrap();
+    labeler
+        .verify_tree("/non-existent", false, |_, _, _| {})
+        .unwrap_err();
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let sub_dir = dir.path().join("sub");
+    std::fs::create_dir(&sub_dir).unwrap();
+    std::fs::write(sub_dir.join("a.txt"), "empty file").unwrap();
+
+    let mut mismatches = 0_u64;
+    let summary = labeler
+        .verify_tree(dir.path(), false, |path, _current, _expected| {
+            assert!(path.starts_with(dir.path()));
+            mismatches += 1;
+        })
+        .unwrap();
+
+    assert_eq!(summary.incorrect, mismatches);
+    assert_eq!(summary.correct + summary.incorrect + summary.skipped, 3);
+    let _ignored = format!("{:?}", &summary);
+}
+
+#[test]
+fn labeler_verify_tree_removed_entry() {
+    use std::io::Write;
+    use std::os::unix::ffi::OsStrExt;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let file_a = dir.path().join("a.txt");
+    let file_b = dir.path().join("b.txt");
+    std::fs::write(&file_a, "empty file").unwrap();
+    std::fs::write(&file_b, "empty file").unwrap();
+
+    // Every file system object under `dir` has an unexpected context.
+    let mut spec_file = tempfile::NamedTempFile::new().unwrap();
+    let spec = format!("{}(/.*)? system_u:object_r:etc_t:s0", dir.path().display());
+    writeln!(spec_file, "{}", spec).unwrap();
+    spec_file.flush().unwrap();
+
+    let c_path = std::ffi::CString::new(spec_file.path().as_os_str().as_bytes()).unwrap();
+    let options = &[(selinux_sys::SELABEL_OPT_PATH, c_path.as_ptr().cast())];
+    let labeler = super::Labeler::<super::back_end::File>::new(options, true).unwrap();
+
+    // The file visited first removes the other one, before it is visited.
+    let summary = labeler
+        .verify_tree(dir.path(), false, |path, _current, _expected| {
+            if path == file_a {
+                std::fs::remove_file(&file_b).unwrap();
+            } else if path == file_b {
+                std::fs::remove_file(&file_a).unwrap();
+            }
+        })
+        .unwrap();
+
+    assert_eq!(summary.incorrect, 2);
+    assert_eq!(summary.skipped, 1);
+}
+
 #[test]
 fn labeler_look_up_best_match_by_path() {
     for &raw_format in &[false, true] {
@@ -83,6 +255,103 @@ fn labeler_get_digests_all_partial_matches_by_path() {
     }
 }
 
+#[test]
+fn labeler_look_up_column() {
+    use std::io::Write;
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    writeln!(
+        file,
+        "db_column public.users.password system_u:object_r:sepgsql_secret_table_t:s0"
+    )
+    .unwrap();
+    file.flush().unwrap();
+
+    let c_path = std::ffi::CString::new(file.path().as_os_str().as_bytes()).unwrap();
+    let options = &[(selinux_sys::SELABEL_OPT_PATH, c_path.as_ptr().cast())];
+    let labeler = super::Labeler::<super::back_end::DB>::new(options, true).unwrap();
+
+    let context = labeler
+        .look_up_column(Some("public"), "users", "password")
+        .unwrap();
+    assert_eq!(
+        context.as_bytes(),
+        b"system_u:object_r:sepgsql_secret_table_t:s0"
+    );
+
+    labeler
+        .look_up_column(Some("public"), "users", "name")
+        .unwrap_err();
+}
+
+#[test]
+fn labeler_look_up_database_and_schema() {
+    use std::io::Write;
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    writeln!(file, "db_database sales system_u:object_r:sepgsql_db_t:s0").unwrap();
+    writeln!(
+        file,
+        "db_schema public system_u:object_r:sepgsql_schema_t:s0"
+    )
+    .unwrap();
+    file.flush().unwrap();
+
+    let c_path = std::ffi::CString::new(file.path().as_os_str().as_bytes()).unwrap();
+    let options = &[(selinux_sys::SELABEL_OPT_PATH, c_path.as_ptr().cast())];
+    let labeler = super::Labeler::<super::back_end::DB>::new(options, true).unwrap();
+
+    let context = labeler.look_up_database("sales").unwrap();
+    assert_eq!(context.as_bytes(), b"system_u:object_r:sepgsql_db_t:s0");
+    labeler.look_up_database("public").unwrap_err();
+
+    let context = labeler.look_up_schema("public").unwrap();
+    assert_eq!(context.as_bytes(), b"system_u:object_r:sepgsql_schema_t:s0");
+    labeler.look_up_schema("sales").unwrap_err();
+}
+
+#[test]
+fn labeler_from_spec_file() {
+    use std::io::Write;
+
+    let mut file = tempfile::tempfile().unwrap();
+    writeln!(
+        file,
+        "db_column public.users.password system_u:object_r:sepgsql_secret_table_t:s0"
+    )
+    .unwrap();
+
+    let labeler = super::Labeler::<super::back_end::DB>::from_spec_file(&file, &[], true).unwrap();
+    drop(file);
+
+    let context = labeler
+        .look_up_column(Some("public"), "users", "password")
+        .unwrap();
+    assert_eq!(
+        context.as_bytes(),
+        b"system_u:object_r:sepgsql_secret_table_t:s0"
+    );
+}
+
+#[test]
+fn database_object_name() {
+    for &(schema, table, column, expected) in &[
+        (None, "users", None, "users"),
+        (Some("public"), "users", None, "public.users"),
+        (None, "users", Some("password"), "users.password"),
+        (
+            Some("public"),
+            "users",
+            Some("password"),
+            "public.users.password",
+        ),
+    ] {
+        assert_eq!(super::database_object_name(schema, table, column), expected);
+    }
+}
+
 #[test]
 fn digest() {
     let sf_two_nulls = &[ptr::null(), ptr::null()];
@@ -208,3 +477,21 @@ fn partial_matches_digests() {
 
     let _ignored = format!("{:?}", &pmd);
 }
+
+#[test]
+fn regex_escape_path() {
+    assert_eq!(super::regex_escape_path("/srv/www"), "/srv/www");
+    assert_eq!(
+        super::regex_escape_path("/srv/a.b[1]/(x)|y+z*?^$\\{2}"),
+        "/srv/a\\.b\\[1\\]/\\(x\\)\\|y\\+z\\*\\?\\^\\$\\\\\\{2\\}"
+    );
+}
+
+#[test]
+fn regex_anchor_path() {
+    assert_eq!(
+        super::regex_anchor_path("/srv/www.example"),
+        "/srv/www\\.example(/.*)?"
+    );
+    assert_eq!(super::regex_anchor_path("/srv/"), "/srv(/.*)?");
+}
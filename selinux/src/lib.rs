@@ -40,12 +40,14 @@ use std::borrow::Cow;
 use std::collections::{hash_map, HashMap};
 use std::convert::TryFrom;
 use std::ffi::{CStr, CString};
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 use std::mem::MaybeUninit;
 use std::os::raw::{c_char, c_int, c_uint, c_void};
 use std::os::unix::io::AsRawFd;
-use std::path::Path;
-use std::{cmp, fmt, io, mem, ptr, slice, str};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::{cmp, env, fmt, fs, io, mem, ptr, slice, str};
 
 use selinux_sys::pid_t;
 
@@ -112,6 +114,26 @@ impl LayerColors {
             foreground,
         }
     }
+
+    /// Return the ANSI escape sequence that sets the background and foreground
+    /// colors of a terminal to these colors.
+    ///
+    /// The terminal must support 24-bit colors.
+    #[must_use]
+    pub fn to_ansi_sequence(&self) -> String {
+        let (bg, fg) = (self.background, self.foreground);
+        format!(
+            "\x1b[48;2;{};{};{}m\x1b[38;2;{};{};{}m",
+            bg.red, bg.green, bg.blue, fg.red, fg.green, fg.blue
+        )
+    }
+
+    /// Return the ANSI escape sequence that resets the background and
+    /// foreground colors of a terminal to their defaults.
+    #[must_use]
+    pub fn ansi_reset_sequence() -> &'static str {
+        "\x1b[0m"
+    }
 }
 
 /// Colors of a security context.
@@ -226,6 +248,163 @@ impl<'context> SecurityContext<'context> {
         }
     }
 
+    /// Return the security context stored in the environment variable `name`,
+    /// or `None` if the variable is not set.
+    ///
+    /// This returns an error if the variable holds an invalid security context.
+    /// The context is not validated if SELinux is not enabled.
+    ///
+    /// See: `security_check_context()`.
+    #[doc(alias = "security_check_context")]
+    pub fn from_env(name: &str, raw_format: bool) -> Result<Option<Self>> {
+        use std::os::unix::ffi::OsStringExt;
+
+        let value = match env::var_os(name) {
+            Some(value) => value.into_vec(),
+            None => return Ok(None),
+        };
+
+        let c_context = CString::new(value).map_err(|_r| {
+            Error::from_io_name("env::var_os()", name, io::ErrorKind::InvalidData.into())
+        })?;
+
+        if SecurityContext::from_c_str(&c_context, raw_format).check() == Some(false) {
+            let err = io::ErrorKind::InvalidData.into();
+            return Err(Error::from_io_name("security_check_context()", name, err));
+        }
+
+        Self::from_c_str_copy(&c_context, raw_format).map(Some)
+    }
+
+    /// Store this security context in the environment variable `name`.
+    ///
+    /// This returns an error if `name` is not a valid environment variable
+    /// name.
+    ///
+    /// ⚠️ This modifies the process environment through [`env::set_var`], which
+    /// is only sound while no other thread reads or writes the environment,
+    /// including through C functions such as `getenv()`. Call this only while
+    /// the process is single-threaded, e.g., before spawning threads.
+    pub fn set_env(&self, name: &str) -> Result<()> {
+        use std::os::unix::ffi::OsStrExt;
+
+        if name.is_empty() || name.contains(&['=', '\0'][..]) {
+            let err = io::ErrorKind::InvalidInput.into();
+            return Err(Error::from_io_name("env::set_var()", name, err));
+        }
+
+        let value = self.to_c_string()?;
+        let value = value
+            .as_ref()
+            .map_or(&[] as &[u8], |value| value.to_bytes());
+        env::set_var(name, std::ffi::OsStr::from_bytes(value));
+        Ok(())
+    }
+
+    /// Return this security context percent-encoded, e.g., for use as a URL
+    /// path segment or query parameter.
+    ///
+    /// All bytes except ASCII alphanumeric characters and `-`, `.`, `_` and `~`
+    /// are encoded, including `:` as `%3A` and `/` as `%2F`.
+    #[must_use]
+    pub fn to_url_encoded(&self) -> String {
+        let mut encoded = String::with_capacity(self.value_bytes().len());
+        for &byte in self.value_bytes() {
+            if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+                encoded.push(char::from(byte));
+            } else {
+                encoded.push_str(&format!("%{:02X}", byte));
+            }
+        }
+        encoded
+    }
+
+    /// Decode a security context percent-encoded by
+    /// [`SecurityContext::to_url_encoded`].
+    ///
+    /// This returns an error if `encoded` is not validly percent-encoded,
+    /// or if it holds an invalid security context.
+    /// The context is not validated if SELinux is not enabled.
+    ///
+    /// See: `security_check_context()`.
+    #[doc(alias = "security_check_context")]
+    pub fn from_url_encoded(encoded: &str, raw_format: bool) -> Result<SecurityContext<'static>> {
+        let invalid = || {
+            let err = io::ErrorKind::InvalidData.into();
+            Error::from_io_name("SecurityContext::from_url_encoded()", encoded, err)
+        };
+
+        let mut bytes = Vec::with_capacity(encoded.len());
+        let mut iter = encoded.bytes();
+        while let Some(byte) = iter.next() {
+            if byte == b'%' {
+                let mut next_digit = || {
+                    iter.next()
+                        .filter(u8::is_ascii_hexdigit)
+                        .ok_or_else(invalid)
+                };
+                let digits = [next_digit()?, next_digit()?];
+                let digits = str::from_utf8(&digits).map_err(|_r| invalid())?;
+                bytes.push(u8::from_str_radix(digits, 16).map_err(|_r| invalid())?);
+            } else {
+                bytes.push(byte);
+            }
+        }
+
+        let c_context = CString::new(bytes).map_err(|_r| invalid())?;
+
+        if SecurityContext::from_c_str(&c_context, raw_format).check() == Some(false) {
+            let err = io::ErrorKind::InvalidData.into();
+            return Err(Error::from_io_name(
+                "security_check_context()",
+                encoded,
+                err,
+            ));
+        }
+
+        SecurityContext::from_c_str_copy(&c_context, raw_format)
+    }
+
+    /// Return the security context of init scripts, read from the file
+    /// returned by [`path::init_scripts_context`].
+    ///
+    /// Empty lines and lines starting with `#` are ignored.
+    ///
+    /// See: `selinux_contexts_path()`.
+    #[doc(alias = "selinux_contexts_path")]
+    pub fn of_init_scripts(raw_format: bool) -> Result<Self> {
+        let path = path::init_scripts_context()?;
+        let bytes = fs::read(&path).map_err(|err| Error::from_io_path("fs::read()", &path, err))?;
+
+        let line = str::from_utf8(&bytes)?
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('#'))
+            .ok_or_else(|| {
+                let err = io::ErrorKind::InvalidData.into();
+                Error::from_io_path("SecurityContext::of_init_scripts()", &path, err)
+            })?;
+
+        let c_context = CString::new(line).map_err(|_r| {
+            Error::from_io_path("CString::new()", &path, io::ErrorKind::InvalidData.into())
+        })?;
+        Self::from_c_str_copy(&c_context, raw_format)
+    }
+
+    /// Store this security context as the security context of init scripts,
+    /// in the file returned by [`path::init_scripts_context`].
+    ///
+    /// See: `selinux_contexts_path()`.
+    #[doc(alias = "selinux_contexts_path")]
+    pub fn set_for_init_scripts(&self) -> Result<()> {
+        let path = path::init_scripts_context()?;
+        let mut bytes = self
+            .to_c_string()?
+            .map_or_else(Vec::new, |context| context.to_bytes().to_vec());
+        bytes.push(b'\n');
+        fs::write(&path, bytes).map_err(|err| Error::from_io_path("fs::write()", &path, err))
+    }
+
     /// Return the security context of the current process.
     ///
     /// See: `getcon()`.
@@ -291,6 +470,21 @@ impl<'context> SecurityContext<'context> {
         Self::from_result_with_name(proc_name, r, context, name, raw_format)
     }
 
+    /// Get the names and contexts of all kernel initial security identifiers,
+    /// sorted by name.
+    ///
+    /// See: `security_get_initial_context()`.
+    #[doc(alias = "security_get_initial_context")]
+    pub fn of_initial_kernel_contexts(raw_format: bool) -> Result<Vec<(String, Self)>> {
+        initial_kernel_context_names()?
+            .into_iter()
+            .map(|name| {
+                let context = Self::of_initial_kernel_context(&name, raw_format)?;
+                Ok((name, context))
+            })
+            .collect()
+    }
+
     /// Get the default SELinux security context for the specified media type
     /// from the policy.
     ///
@@ -319,6 +513,41 @@ impl<'context> SecurityContext<'context> {
         Self::from_result_with_pid(proc_name, r, context, process_id, raw_format)
     }
 
+    /// Return the process contexts of all processes listed in `/proc`,
+    /// sorted by process identifier.
+    ///
+    /// Processes that terminate while they are being enumerated are skipped.
+    ///
+    /// See: `getpidcon()`.
+    #[doc(alias = "getpidcon")]
+    pub fn of_all_processes(raw_format: bool) -> Result<Vec<(pid_t, Self)>> {
+        let path = Path::new("/proc");
+        let to_error = |err| Error::from_io_path("fs::read_dir()", path, err);
+
+        let mut contexts = Vec::new();
+        for entry in fs::read_dir(path).map_err(to_error)? {
+            let entry = entry.map_err(to_error)?;
+            let process_id = match entry.file_name().to_str().map(str::parse::<pid_t>) {
+                Some(Ok(process_id)) => process_id,
+                _ => continue,
+            };
+
+            match Self::of_process(process_id, raw_format) {
+                Ok(context) => contexts.push((process_id, context)),
+
+                Err(err) => {
+                    let errno = err.io_source().and_then(io::Error::raw_os_error);
+                    if errno != Some(libc::ENOENT) && errno != Some(libc::ESRCH) {
+                        return Err(err);
+                    }
+                }
+            }
+        }
+
+        contexts.sort_unstable_by_key(|&(process_id, _)| process_id);
+        Ok(contexts)
+    }
+
     /// Perform context translation from the human-readable format (translated)
     /// to the internal system format (raw).
     ///
@@ -447,6 +676,33 @@ impl<'context> SecurityContext<'context> {
         Self::from_result(proc_name, r, context, raw_format)
     }
 
+    /// Obtain the fail-safe context for the specified SELinux user identity,
+    /// used for emergency logins when no default context is usable.
+    ///
+    /// The fail-safe file holds a `role:type[:level]` entry; the user identity
+    /// is prepended to it. This returns an error of kind
+    /// [`io::ErrorKind::NotFound`] if the file holds no entry.
+    ///
+    /// See: `selinux_failsafe_context_path()`.
+    #[doc(alias = "selinux_failsafe_context_path")]
+    pub fn fail_safe_for_se_user(se_user: &str, raw_format: bool) -> Result<Self> {
+        let path = path::fail_safe_context()?;
+        let contents = fs::read_to_string(path)
+            .map_err(|err| Error::from_io_path("fs::read_to_string()", path, err))?;
+
+        let entry = contents
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('#'))
+            .ok_or_else(|| {
+                let err = io::ErrorKind::NotFound.into();
+                Error::from_io_path("SecurityContext::fail_safe_for_se_user()", path, err)
+            })?;
+
+        let c_context = str_to_c_string(&format!("{}:{}", se_user, entry))?;
+        Self::from_c_str_copy(&c_context, raw_format)
+    }
+
     /// Get the context used for executing a new process.
     ///
     /// See: `getexeccon()`.
@@ -599,6 +855,23 @@ impl<'context> SecurityContext<'context> {
         ret_val_to_result(proc_name, unsafe { proc(self.context.as_ptr()) })
     }
 
+    /// Reset the contexts used for the next `execve()` call, and for creating
+    /// new file system objects, labeled sockets and kernel key rings, to the
+    /// default policy behavior.
+    ///
+    /// See: `setexeccon()`, `setfscreatecon()`, `setsockcreatecon()`,
+    /// `setkeycreatecon()`.
+    #[doc(alias = "setexeccon")]
+    #[doc(alias = "setfscreatecon")]
+    #[doc(alias = "setsockcreatecon")]
+    #[doc(alias = "setkeycreatecon")]
+    pub fn set_default_contexts_for_new_operations() -> Result<()> {
+        Self::set_default_context_for_next_exec()?;
+        Self::set_default_context_for_new_file_system_objects()?;
+        Self::set_default_context_for_new_labeled_sockets()?;
+        Self::set_default_context_for_new_kernel_key_rings()
+    }
+
     /// Get the context associated with the given path in the file system.
     ///
     /// See: `lgetfilecon()`, `getfilecon()`.
@@ -669,6 +942,108 @@ impl<'context> SecurityContext<'context> {
         ret_val_to_result_with_path(proc_name, r, path.as_ref())
     }
 
+    /// Change the SELinux security context of a file system object, similarly
+    /// to the `chcon` command.
+    ///
+    /// If any of the [`USER`], [`ROLE`], [`TYPE`] or [`RANGE`] flags is set,
+    /// then only the selected components of the current context of each file
+    /// system object are replaced by those of this context.
+    /// Otherwise, the whole context is replaced.
+    ///
+    /// If the [`RECURSE`] flag is set, then directories are descended.
+    /// The [`FOLLOW_SYMBOLIC_LINKS`] flag applies only to `path` itself, similarly
+    /// to `chcon -R -H`: symbolic links found while descending directories are
+    /// neither traversed nor dereferenced, so their own contexts are changed,
+    /// and objects outside the descended tree are never relabeled.
+    ///
+    /// See: `lsetfilecon()`, `setfilecon()`.
+    ///
+    /// [`USER`]: ChangeContextFlags::USER
+    /// [`ROLE`]: ChangeContextFlags::ROLE
+    /// [`TYPE`]: ChangeContextFlags::TYPE
+    /// [`RANGE`]: ChangeContextFlags::RANGE
+    /// [`RECURSE`]: ChangeContextFlags::RECURSE
+    /// [`FOLLOW_SYMBOLIC_LINKS`]: ChangeContextFlags::FOLLOW_SYMBOLIC_LINKS
+    #[doc(alias = "chcon")]
+    #[doc(alias = "lsetfilecon")]
+    #[doc(alias = "setfilecon")]
+    pub fn change_for_path(&self, path: impl AsRef<Path>, flags: ChangeContextFlags) -> Result<()> {
+        let path = path.as_ref();
+        self.change_for_single_path(path, flags)?;
+
+        if !flags.contains(ChangeContextFlags::RECURSE) {
+            return Ok(());
+        }
+
+        let follow_symbolic_links = flags.contains(ChangeContextFlags::FOLLOW_SYMBOLIC_LINKS);
+        let metadata = if follow_symbolic_links {
+            fs::metadata(path).map_err(|err| Error::from_io_path("fs::metadata()", path, err))?
+        } else {
+            fs::symlink_metadata(path)
+                .map_err(|err| Error::from_io_path("fs::symlink_metadata()", path, err))?
+        };
+
+        if metadata.is_dir() {
+            let entry_flags = flags - ChangeContextFlags::FOLLOW_SYMBOLIC_LINKS;
+            self.change_for_directory_entries(path, entry_flags)?;
+        }
+        Ok(())
+    }
+
+    fn change_for_directory_entries(&self, path: &Path, flags: ChangeContextFlags) -> Result<()> {
+        let to_error = |err| Error::from_io_path("fs::read_dir()", path, err);
+
+        for entry in fs::read_dir(path).map_err(to_error)? {
+            let entry = entry.map_err(to_error)?;
+            let entry_path = entry.path();
+            self.change_for_single_path(&entry_path, flags)?;
+
+            let file_type = entry.file_type().map_err(to_error)?;
+            if file_type.is_dir() {
+                self.change_for_directory_entries(&entry_path, flags)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn change_for_single_path(&self, path: &Path, flags: ChangeContextFlags) -> Result<()> {
+        let follow_symbolic_links = flags.contains(ChangeContextFlags::FOLLOW_SYMBOLIC_LINKS);
+        let components = flags
+            & (ChangeContextFlags::USER
+                | ChangeContextFlags::ROLE
+                | ChangeContextFlags::TYPE
+                | ChangeContextFlags::RANGE);
+        if components.is_empty() {
+            return self.set_for_path(path, follow_symbolic_links, self.is_raw);
+        }
+
+        let current =
+            Self::of_path(path, follow_symbolic_links, self.is_raw)?.ok_or_else(|| {
+                let err = io::Error::from_raw_os_error(libc::ENODATA);
+                Error::from_io_path("SecurityContext::of_path()", path, err)
+            })?;
+
+        let context = current.to_opaque()?;
+        let new_components = self.to_opaque()?;
+
+        if components.contains(ChangeContextFlags::USER) {
+            context.set_user(&new_components.user()?)?;
+        }
+        if components.contains(ChangeContextFlags::ROLE) {
+            context.set_role(&new_components.role()?)?;
+        }
+        if components.contains(ChangeContextFlags::TYPE) {
+            context.set_type(&new_components.the_type()?)?;
+        }
+        if components.contains(ChangeContextFlags::RANGE) {
+            context.set_range(&new_components.range()?)?;
+        }
+
+        let c_context = context.to_c_string()?;
+        let new_context = SecurityContext::from_c_str(&c_context, self.is_raw);
+        new_context.set_for_path(path, follow_symbolic_links, self.is_raw)
+    }
+
     /// Get the SELinux security context of a file system object.
     ///
     /// See: `fgetfilecon()`.
@@ -719,6 +1094,31 @@ impl<'context> SecurityContext<'context> {
         ret_val_to_result(proc_name, r)
     }
 
+    /// Create an anonymous memory-backed file, as `memfd_create()` does,
+    /// labeled with this security context.
+    ///
+    /// The file descriptor is created with the close-on-exec flag set.
+    /// This returns an error if the kernel does not support labeling memory
+    /// files.
+    ///
+    /// See: `memfd_create()`, `fsetfilecon()`.
+    #[doc(alias = "memfd_create")]
+    #[doc(alias = "fsetfilecon")]
+    pub fn create_memory_file(&self, name: &str) -> Result<fs::File> {
+        use std::os::unix::io::FromRawFd;
+
+        let c_name = str_to_c_string(name)?;
+        let fd = unsafe { libc::memfd_create(c_name.as_ptr(), libc::MFD_CLOEXEC) };
+        if fd == -1_i32 {
+            let err = io::Error::last_os_error();
+            return Err(Error::from_io_name("memfd_create()", name, err));
+        }
+
+        let file = unsafe { fs::File::from_raw_fd(fd) };
+        self.set_for_file(&file)?;
+        Ok(file)
+    }
+
     /// Set the SELinux security context of the peer socket identified by an
     /// open file descriptor.
     ///
@@ -887,6 +1287,30 @@ impl<'context> SecurityContext<'context> {
         Self::from_result(proc_name, r, context, self.is_raw)
     }
 
+    /// Compute the contexts to use when labeling a polyinstantiated
+    /// object instance, for each one of the `source_contexts`.
+    ///
+    /// The returned list has one element for each element of `source_contexts`,
+    /// in the same order.
+    /// A failure to compute a context is reported in the corresponding element,
+    /// and does not prevent computing the remaining contexts.
+    ///
+    /// See: `security_compute_member()`.
+    #[doc(alias = "security_compute_member")]
+    #[must_use]
+    pub fn of_polyinstantiation_member_decisions(
+        source_contexts: &[Self],
+        target_context: &Self,
+        target_class: SecurityClass,
+    ) -> Vec<Result<Self>> {
+        source_contexts
+            .iter()
+            .map(|source_context| {
+                source_context.of_polyinstantiation_member_decision(target_context, target_class)
+            })
+            .collect()
+    }
+
     /// Determine if a transition from this context to `new_context` using
     /// `target_context` as the object is valid for object class `target_class`.
     ///
@@ -926,6 +1350,156 @@ impl<'context> SecurityContext<'context> {
         ret_val_to_result(proc_name, r)
     }
 
+    /// Return whether the policy permits this context to perform `operation`
+    /// on a database column labeled with `column_context`.
+    ///
+    /// The operation is checked against the `db_column` security class.
+    /// Row deletions are checked on tables rather than on columns, so they are
+    /// not column operations.
+    ///
+    /// See: `security_compute_av_flags()`.
+    #[doc(alias = "security_compute_av_flags")]
+    pub fn check_column_access(
+        &self,
+        column_context: &Self,
+        operation: DatabaseColumnOperation,
+    ) -> Result<bool> {
+        let target_class = SecurityClass::from_name("db_column")?;
+        let permission = target_class.access_vector_bit(operation.permission_name())?;
+        let decision = self.query_access_decision(column_context, target_class, permission)?;
+        Ok((decision.allowed & permission) == permission)
+    }
+
+    /// Return whether the policy permits this context to perform `operation`
+    /// on a database row labeled with `row_context`.
+    ///
+    /// The operation is checked against the `db_tuple` security class.
+    /// Decisions are computed by the kernel on each call; use
+    /// [`avc::AccessVectorCache`] to cache them.
+    ///
+    /// See: `security_compute_av_flags()`.
+    #[doc(alias = "security_compute_av_flags")]
+    pub fn check_row_access(
+        &self,
+        row_context: &Self,
+        operation: DatabaseRowOperation,
+    ) -> Result<bool> {
+        let target_class = SecurityClass::from_name("db_tuple")?;
+        let permission = target_class.access_vector_bit(operation.permission_name())?;
+        let decision = self.query_access_decision(row_context, target_class, permission)?;
+        Ok((decision.allowed & permission) == permission)
+    }
+
+    /// Return whether the domain of this context is permissive, i.e., whether
+    /// the policy marks its type as permissive, so that its denials are
+    /// logged but not enforced.
+    ///
+    /// This does not report the system-wide enforcing mode; see
+    /// [`current_mode`] for that.
+    ///
+    /// See: `security_compute_av_flags()`.
+    #[doc(alias = "security_compute_av_flags")]
+    pub fn is_domain_permissive(&self) -> Result<bool> {
+        let target_class = SecurityClass::from_name("process")?;
+        let decision = self.query_access_decision(self, target_class, 0)?;
+        Ok((decision.flags & selinux_sys::SELINUX_AVD_FLAGS_PERMISSIVE as c_uint) != 0)
+    }
+
+    /// Return whether the policy permits this context to access `object_context`
+    /// via `target_class` with `requested_permission`, distinguishing enforced
+    /// denials from denials that are not enforced, either because the domain of
+    /// this context is permissive, or because the system is not enforcing, as
+    /// reported by [`current_mode`].
+    ///
+    /// See: `security_compute_av_flags()`, `security_getenforce()`.
+    #[doc(alias = "security_compute_av_flags")]
+    #[doc(alias = "security_getenforce")]
+    pub fn check_access_respecting_permissive_mode(
+        &self,
+        object_context: &Self,
+        target_class: &str,
+        requested_permission: &str,
+    ) -> Result<AccessResult> {
+        let target_class = SecurityClass::from_name(target_class)?;
+        let permission = target_class.access_vector_bit(requested_permission)?;
+        let decision = self.query_access_decision(object_context, target_class, permission)?;
+
+        if (decision.allowed & permission) == permission {
+            Ok(AccessResult::Allowed)
+        } else if (decision.flags & selinux_sys::SELINUX_AVD_FLAGS_PERMISSIVE as c_uint) != 0
+            || !matches!(current_mode(), SELinuxMode::Enforcing)
+        {
+            Ok(AccessResult::DeniedButPermissive)
+        } else {
+            Ok(AccessResult::Denied)
+        }
+    }
+
+    /// Return whether the policy permits this context to transition to
+    /// the domain `target_type`.
+    ///
+    /// The domain is formed by replacing the type of this context by
+    /// `target_type`. This returns an error if the resulting context is invalid.
+    ///
+    /// See: `security_compute_av_flags()`.
+    #[doc(alias = "security_compute_av_flags")]
+    pub fn can_transition_to_type(&self, target_type: &str) -> Result<bool> {
+        let target_context = self.to_opaque()?;
+        target_context.set_type_str(target_type)?;
+        let c_target_context = target_context.to_c_string()?;
+        let target_context = SecurityContext::from_c_str(&c_target_context, self.is_raw);
+
+        let target_class = SecurityClass::from_name("process")?;
+        let transition = target_class.access_vector_bit("transition")?;
+        let decision = self.query_access_decision(&target_context, target_class, transition)?;
+        Ok((decision.allowed & transition) == transition)
+    }
+
+    /// Return a copy of this security context whose MLS range is replaced by
+    /// the lowest sensitivity, `s0`, without categories.
+    ///
+    /// This is only meaningful on MLS policies; it returns an error if this
+    /// context has no range.
+    ///
+    /// See: `context_range_set()`.
+    #[doc(alias = "context_range_set")]
+    pub fn with_default_sensitivity(&self) -> Result<SecurityContext<'static>> {
+        let context = self.to_opaque()?;
+        context.range()?;
+        context.set_range_str("s0")?;
+        SecurityContext::from_c_str_copy(&context.to_c_string()?, self.is_raw)
+    }
+
+    /// Return a copy of this security context whose MLS levels have their
+    /// sensitivity replaced by `sensitivity`, e.g., `s2`, keeping their
+    /// categories.
+    ///
+    /// For example, with a `sensitivity` of `s2`, the range `s0-s1:c0.c5`
+    /// becomes `s2-s2:c0.c5`.
+    /// This is only meaningful on MLS policies; it returns an error if this
+    /// context has no range.
+    ///
+    /// See: `context_range_get()`, `context_range_set()`.
+    #[doc(alias = "context_range_get")]
+    #[doc(alias = "context_range_set")]
+    pub fn with_sensitivity(&self, sensitivity: &str) -> Result<SecurityContext<'static>> {
+        let context = self.to_opaque()?;
+        let range = context.range()?;
+        let range = range.to_str()?;
+
+        let new_range = range
+            .split('-')
+            .map(|level| match level.split_once(':') {
+                Some((_, categories)) => format!("{}:{}", sensitivity, categories),
+                None => sensitivity.into(),
+            })
+            .collect::<Vec<_>>()
+            .join("-");
+
+        context.set_range_str(&new_range)?;
+        SecurityContext::from_c_str_copy(&context.to_c_string()?, self.is_raw)
+    }
+
     /// Check the validity of an SELinux context.
     ///
     /// See: `security_check_context()`, `is_selinux_enabled()`.
@@ -950,8 +1524,22 @@ impl<'context> SecurityContext<'context> {
         }
     }
 
+    /// Check the syntax of a raw security context string, without consulting
+    /// the policy.
+    ///
+    /// This is equivalent to `FormatChecker::default().check(context)`.
+    #[must_use]
+    pub fn check_format(context: &str) -> FormatCheckResult {
+        FormatChecker::default().check(context)
+    }
+
     /// Canonicalize this security context.
     ///
+    /// The kernel returns the canonical form of this context, e.g., with the
+    /// categories of the MLS range sorted and expressed as ranges where possible.
+    /// Two contexts differing only by the ordering of their categories therefore
+    /// have the same canonical form.
+    ///
     /// See: `security_canonicalize_context()`.
     #[doc(alias = "security_canonicalize_context")]
     pub fn canonicalize(&self) -> Result<Self> {
@@ -997,6 +1585,40 @@ impl<'context> SecurityContext<'context> {
         Ok(r == 0_i32)
     }
 
+    /// Return whether the policy allows a context formed by replacing the range
+    /// of this context by `range` to contain this context, as `pam_selinux`
+    /// checks before assigning a requested MLS range to a user session.
+    ///
+    /// ⚠️ This is not a pure check that the level of this context is within
+    /// `range`. The decision is the `contains` permission of the `context`
+    /// class, which combines a type enforcement rule with the MLS constraint
+    /// on that permission. Unless the policy has an
+    /// `allow T self:context contains;` rule for the type `T` of this context,
+    /// this returns `false` whatever the levels are. Policies usually have such
+    /// rules only for login and `newrole` domains.
+    ///
+    /// This returns an error if the loaded policy does not support MLS.
+    ///
+    /// See: `is_selinux_mls_enabled()`, `security_compute_av_flags()`.
+    #[doc(alias = "is_selinux_mls_enabled")]
+    pub fn is_mls_range_allowed(&self, range: &str) -> Result<bool> {
+        if unsafe { selinux_sys::is_selinux_mls_enabled() } == 0_i32 {
+            let err = io::ErrorKind::Unsupported.into();
+            return Err(Error::from_io("is_selinux_mls_enabled()", err));
+        }
+
+        let target_class = SecurityClass::from_name("context")?;
+        let contains = target_class.access_vector_bit("contains")?;
+
+        let range_context = self.to_opaque()?;
+        range_context.set_range_str(range)?;
+        let c_range_context = range_context.to_c_string()?;
+        let range_context = SecurityContext::from_c_str(&c_range_context, self.is_raw);
+
+        let decision = range_context.query_access_decision(self, target_class, contains)?;
+        Ok((decision.allowed & contains) == contains)
+    }
+
     /// Check whether a SELinux tty security context is defined as
     /// a securetty context.
     ///
@@ -1048,6 +1670,17 @@ impl<'context> SecurityContext<'context> {
         }
     }
 
+    /// Return the color string for this SELinux security context, or `None`
+    /// if colors cannot be determined, e.g., because the `secolor.conf` file
+    /// does not exist.
+    ///
+    /// See: `selinux_raw_context_to_color()`.
+    #[doc(alias = "selinux_raw_context_to_color")]
+    #[must_use]
+    pub fn to_color_if_configured(&self) -> Option<SecurityContextColors> {
+        self.to_color().ok()
+    }
+
     /// Compare this SELinux security context with another one, excluding
     /// the `user` component.
     ///
@@ -1062,6 +1695,21 @@ impl<'context> SecurityContext<'context> {
         r.cmp(&0_i32)
     }
 
+    /// Return `true` if this SELinux security context has the same `user`,
+    /// `role` and `type` components as another one, regardless of their
+    /// `range` components.
+    ///
+    /// See: `context_new()`.
+    #[doc(alias = "context_new")]
+    pub fn eq_range_insensitive(&self, other: &Self) -> Result<bool> {
+        let context = self.to_opaque()?;
+        let other = other.to_opaque()?;
+
+        Ok(context.user()? == other.user()?
+            && context.role()? == other.role()?
+            && context.the_type()? == other.the_type()?)
+    }
+
     /// Compare the SELinux security context on disk to the default security
     /// context required by the policy file contexts file.
     ///
@@ -1113,6 +1761,27 @@ impl<'context> SecurityContext<'context> {
         }
     }
 
+    fn to_opaque(&self) -> Result<OpaqueSecurityContext> {
+        let c_context = self.to_c_string()?.ok_or_else(|| {
+            let err = io::ErrorKind::InvalidData.into();
+            Error::from_io("SecurityContext::to_c_string()", err)
+        })?;
+
+        OpaqueSecurityContext::from_c_str(&c_context)
+    }
+
+    fn value_bytes(&self) -> &[u8] {
+        let bytes = self.as_bytes();
+        bytes.strip_suffix(b"\0").unwrap_or(bytes)
+    }
+
+    fn from_c_str_copy(c_context: &CStr, raw_format: bool) -> Result<Self> {
+        let context = unsafe { libc::strdup(c_context.as_ptr()) };
+        ptr::NonNull::new(context)
+            .map(|context| Self::from_ptr(context, None, raw_format))
+            .ok_or_else(|| Error::last_io_error("strdup()"))
+    }
+
     fn from_ptr(context: ptr::NonNull<c_char>, size: Option<c_uint>, raw_format: bool) -> Self {
         Self {
             context,
@@ -1229,6 +1898,23 @@ impl<'context> SecurityContext<'context> {
     }
 }
 
+impl<'context> PartialEq for SecurityContext<'context> {
+    /// Two security contexts are equal if they have the same string value
+    /// and the same format, regardless of where they are stored.
+    fn eq(&self, other: &Self) -> bool {
+        self.is_raw == other.is_raw && self.value_bytes() == other.value_bytes()
+    }
+}
+
+impl<'context> Eq for SecurityContext<'context> {}
+
+impl<'context> Hash for SecurityContext<'context> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.is_raw.hash(state);
+        self.value_bytes().hash(state);
+    }
+}
+
 impl<'context> Drop for SecurityContext<'context> {
     /// See: `freecon()`.
     #[doc(alias = "freecon")]
@@ -1241,26 +1927,83 @@ impl<'context> Drop for SecurityContext<'context> {
     }
 }
 
-/// List of security contexts.
+/// Guard that sets the contexts used for the next `execve()` call and for
+/// creating new file system objects, and restores their previous values when
+/// dropped.
+///
+/// These contexts are attributes of the calling thread.
 #[derive(Debug)]
-pub struct SecurityContextList {
-    context_list: ptr::NonNull<*mut c_char>,
-    count: usize,
-    _phantom_data: PhantomData<c_char>,
+pub struct ExecContextsGuard {
+    previous_exec_context: Option<SecurityContext<'static>>,
+    previous_file_system_objects_context: Option<SecurityContext<'static>>,
 }
 
-impl SecurityContextList {
-    /// Return the managed raw pointer to [`c_char`].
-    #[must_use]
-    pub fn as_ptr(&self) -> *const *const c_char {
-        self.context_list.as_ptr().cast()
-    }
+impl ExecContextsGuard {
+    /// Set the context used for the next `execve()` call, then the context
+    /// used for creating new file system objects.
+    ///
+    /// The context of the next `execve()` call is set first, so that the
+    /// context of new file system objects is left unchanged if it fails.
+    ///
+    /// See: `setexeccon()`, `setfscreatecon()`.
+    #[doc(alias = "setexeccon")]
+    #[doc(alias = "setfscreatecon")]
+    pub fn new(
+        exec_context: &SecurityContext,
+        file_system_objects_context: &SecurityContext,
+    ) -> Result<Self> {
+        let previous_exec_context = SecurityContext::of_next_exec(exec_context.is_raw)?;
+        let previous_file_system_objects_context =
+            SecurityContext::of_new_file_system_objects(file_system_objects_context.is_raw)?;
 
-    /// Return the managed raw pointer to [`c_char`].
-    #[must_use]
-    pub fn as_mut_ptr(&mut self) -> *mut *mut c_char {
-        self.context_list.as_ptr()
-    }
+        exec_context.set_for_next_exec()?;
+
+        let guard = Self {
+            previous_exec_context,
+            previous_file_system_objects_context,
+        };
+
+        file_system_objects_context
+            .set_for_new_file_system_objects(file_system_objects_context.is_raw)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for ExecContextsGuard {
+    /// See: `setexeccon()`, `setfscreatecon()`.
+    fn drop(&mut self) {
+        let _ignored = match &self.previous_file_system_objects_context {
+            Some(context) => context.set_for_new_file_system_objects(context.is_raw),
+            None => SecurityContext::set_default_context_for_new_file_system_objects(),
+        };
+
+        let _ignored = match &self.previous_exec_context {
+            Some(context) => context.set_for_next_exec(),
+            None => SecurityContext::set_default_context_for_next_exec(),
+        };
+    }
+}
+
+/// List of security contexts.
+#[derive(Debug)]
+pub struct SecurityContextList {
+    context_list: ptr::NonNull<*mut c_char>,
+    count: usize,
+    _phantom_data: PhantomData<c_char>,
+}
+
+impl SecurityContextList {
+    /// Return the managed raw pointer to [`c_char`].
+    #[must_use]
+    pub fn as_ptr(&self) -> *const *const c_char {
+        self.context_list.as_ptr().cast()
+    }
+
+    /// Return the managed raw pointer to [`c_char`].
+    #[must_use]
+    pub fn as_mut_ptr(&mut self) -> *mut *mut c_char {
+        self.context_list.as_ptr()
+    }
 
     /// Number of security contexts present in this list.
     #[must_use]
@@ -1379,6 +2122,59 @@ impl Drop for SecurityContextList {
     }
 }
 
+/// Table of interned security contexts.
+///
+/// Security contexts are identified by their string value and their format,
+/// so that equal contexts share a single instance.
+#[derive(Debug, Default)]
+pub struct SecurityContextTable {
+    /// Interned security contexts, indexed by format, then by string value.
+    contexts: [HashMap<Box<[u8]>, Rc<SecurityContext<'static>>>; 2],
+}
+
+impl SecurityContextTable {
+    /// Create an empty table.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the interned instance equal to `context`, after interning
+    /// `context` if no such instance exists yet.
+    pub fn intern(&mut self, context: SecurityContext<'static>) -> Rc<SecurityContext<'static>> {
+        let contexts = &mut self.contexts[usize::from(context.is_raw)];
+        if let Some(interned) = contexts.get(context.value_bytes()) {
+            Rc::clone(interned)
+        } else {
+            let key = Box::from(context.value_bytes());
+            let context = Rc::new(context);
+            contexts.insert(key, Rc::clone(&context));
+            context
+        }
+    }
+
+    /// Return the interned instance whose string value is `context`,
+    /// if such instance exists.
+    #[must_use]
+    pub fn get(&self, context: &str, raw_format: bool) -> Option<Rc<SecurityContext<'static>>> {
+        self.contexts[usize::from(raw_format)]
+            .get(context.as_bytes())
+            .cloned()
+    }
+
+    /// Return the number of interned security contexts.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.contexts.iter().map(HashMap::len).sum()
+    }
+
+    /// Return `true` if no security contexts are interned.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.contexts.iter().all(HashMap::is_empty)
+    }
+}
+
 /// File access mode.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct FileAccessMode(selinux_sys::mode_t);
@@ -1473,6 +2269,28 @@ impl SecurityClass {
         }
     }
 
+    /// Return the names of the bits set in `access_vector`, in increasing bit
+    /// order.
+    ///
+    /// This returns an error if any bit set in `access_vector` is not defined
+    /// for this security class.
+    ///
+    /// See: `security_av_perm_to_string()`.
+    #[doc(alias = "security_av_perm_to_string")]
+    pub fn access_vector_bit_names(
+        &self,
+        access_vector: selinux_sys::access_vector_t,
+    ) -> Result<Vec<String>> {
+        (0..selinux_sys::access_vector_t::BITS)
+            .map(|index| 1 << index)
+            .filter(|&bit| (access_vector & bit) != 0)
+            .map(|bit| {
+                let name = unsafe { self.access_vector_bit_name(bit) }?;
+                Ok(name.to_str()?.into())
+            })
+            .collect()
+    }
+
     /// Return the access vector bit corresponding to the given name and this
     /// security class.
     ///
@@ -1527,6 +2345,52 @@ impl TryFrom<FileAccessMode> for SecurityClass {
     }
 }
 
+/// Names of the access vector bits of a security class, looked up once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionTable {
+    class: SecurityClass,
+    names: Vec<Option<String>>,
+}
+
+impl PermissionTable {
+    /// Look up the names of all access vector bits defined for `class`.
+    ///
+    /// See: `security_av_perm_to_string()`.
+    #[doc(alias = "security_av_perm_to_string")]
+    pub fn build_for_class(class: SecurityClass) -> Result<Self> {
+        let names = (0..selinux_sys::access_vector_t::BITS)
+            .map(
+                |index| match unsafe { class.access_vector_bit_name(1 << index) } {
+                    Ok(name) => Ok(Some(name.to_str()?.into())),
+                    Err(_) => Ok(None),
+                },
+            )
+            .collect::<Result<_>>()?;
+
+        Ok(Self { class, names })
+    }
+
+    /// Return the security class whose access vector bits are named by this table.
+    #[must_use]
+    pub fn class(&self) -> SecurityClass {
+        self.class
+    }
+
+    /// Return the names of the bits set in `access_vector`, in increasing bit
+    /// order.
+    ///
+    /// Bits that are not defined for the security class are skipped.
+    #[must_use]
+    pub fn decode(&self, access_vector: selinux_sys::access_vector_t) -> Vec<&str> {
+        self.names
+            .iter()
+            .enumerate()
+            .filter(|&(index, _)| (access_vector & (1 << index)) != 0)
+            .filter_map(|(_, name)| name.as_deref())
+            .collect()
+    }
+}
+
 /// Opaque security context.
 #[derive(Debug)]
 pub struct OpaqueSecurityContext {
@@ -1757,6 +2621,206 @@ impl fmt::Display for OpaqueSecurityContext {
     }
 }
 
+bitflags! {
+    /// Flags of [`SecurityContext::change_for_path`].
+    pub struct ChangeContextFlags: u32 {
+        /// Change file and directory contexts recursively (descend directories).
+        const RECURSE = 0x01;
+
+        /// Change the context of the target of a symbolic link, instead of
+        /// the context of the symbolic link itself.
+        ///
+        /// This applies only to the path given to
+        /// [`SecurityContext::change_for_path`], not to the symbolic links
+        /// found while descending directories.
+        const FOLLOW_SYMBOLIC_LINKS = 0x02;
+
+        /// Change only the `user` component of the context.
+        const USER = 0x04;
+
+        /// Change only the `role` component of the context.
+        const ROLE = 0x08;
+
+        /// Change only the `type` component of the context.
+        const TYPE = 0x10;
+
+        /// Change only the `range` component of the context.
+        const RANGE = 0x20;
+    }
+}
+
+/// Result of checking the syntax of a security context string.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FormatCheckResult {
+    /// The context is well formed.
+    Valid,
+    /// The context has fewer than the required fields.
+    TooFewFields {
+        /// Number of `:`-separated fields found.
+        found: usize,
+    },
+    /// The context has more fields than a range can hold.
+    TooManyFields {
+        /// Number of `:`-separated fields found.
+        found: usize,
+    },
+    /// A field is empty.
+    EmptyField {
+        /// Zero-based index of the field.
+        position: usize,
+    },
+    /// The context contains a null byte.
+    NullByte {
+        /// Byte offset of the null byte.
+        position: usize,
+    },
+    /// The context contains a character that is not allowed.
+    InvalidCharacter {
+        /// Byte offset of the character.
+        position: usize,
+        /// The character.
+        character: char,
+    },
+}
+
+/// Syntax checker of raw security context strings, of the form
+/// `user:role:type[:range]`.
+///
+/// Each of the user, role and type fields is made of ASCII alphanumeric
+/// characters and `_`, `.` or `-`. The range may additionally contain `:` and `,`,
+/// and may itself be made of up to three `:`-separated fields,
+/// e.g., `s0:c0-s0:c0.c1023`.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct FormatChecker {
+    require_mls_range: bool,
+}
+
+impl FormatChecker {
+    /// Create a checker accepting contexts with or without a range.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether checked contexts must have a range field.
+    #[must_use]
+    pub fn require_mls_range(mut self, require_mls_range: bool) -> Self {
+        self.require_mls_range = require_mls_range;
+        self
+    }
+
+    /// Check the syntax of the raw security context string `context`.
+    #[must_use]
+    pub fn check(&self, context: &str) -> FormatCheckResult {
+        if let Some(position) = context.find('\0') {
+            return FormatCheckResult::NullByte { position };
+        }
+
+        let invalid = context.char_indices().find(|&(_, character)| {
+            !(character.is_ascii_alphanumeric() || "_.-:,".contains(character))
+        });
+        if let Some((position, character)) = invalid {
+            return FormatCheckResult::InvalidCharacter {
+                position,
+                character,
+            };
+        }
+
+        let fields: Vec<&str> = context.split(':').collect();
+        let min_fields = if self.require_mls_range { 4 } else { 3 };
+        if fields.len() < min_fields {
+            return FormatCheckResult::TooFewFields {
+                found: fields.len(),
+            };
+        }
+        if fields.len() > 6 {
+            return FormatCheckResult::TooManyFields {
+                found: fields.len(),
+            };
+        }
+
+        if let Some(position) = fields.iter().position(|field| field.is_empty()) {
+            return FormatCheckResult::EmptyField { position };
+        }
+
+        if fields[..3].iter().any(|field| field.contains(',')) {
+            let position = context.find(',').unwrap_or_default();
+            return FormatCheckResult::InvalidCharacter {
+                position,
+                character: ',',
+            };
+        }
+
+        FormatCheckResult::Valid
+    }
+}
+
+/// Outcome of [`SecurityContext::check_access_respecting_permissive_mode`].
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum AccessResult {
+    /// Access is allowed by the policy.
+    Allowed,
+    /// Access is denied by the policy, but the denial is not enforced.
+    DeniedButPermissive,
+    /// Access is denied, and the denial is enforced.
+    Denied,
+}
+
+/// Operation on a database column.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum DatabaseColumnOperation {
+    /// Read the column, e.g., with `SELECT` or in a `REFERENCES` constraint.
+    Select,
+    /// Write the column of a new row, with `INSERT`.
+    Insert,
+    /// Write the column of an existing row, with `UPDATE`.
+    Update,
+}
+
+impl DatabaseColumnOperation {
+    /// Return the name of the `db_column` permission checked for this
+    /// operation.
+    #[must_use]
+    pub fn permission_name(self) -> &'static str {
+        match self {
+            Self::Select => "select",
+            Self::Insert => "insert",
+            Self::Update => "update",
+        }
+    }
+}
+
+/// Operation on a database row.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum DatabaseRowOperation {
+    /// Read the row, e.g., with `SELECT`.
+    Select,
+    /// Create the row, with `INSERT`.
+    Insert,
+    /// Modify the row, with `UPDATE`.
+    Update,
+    /// Remove the row, with `DELETE`.
+    Delete,
+}
+
+impl DatabaseRowOperation {
+    /// Return the name of the `db_tuple` permission checked for this
+    /// operation.
+    #[must_use]
+    pub fn permission_name(self) -> &'static str {
+        match self {
+            Self::Select => "select",
+            Self::Insert => "insert",
+            Self::Update => "update",
+            Self::Delete => "delete",
+        }
+    }
+}
+
 /// Support of SELinux in the running kernel.
 #[non_exhaustive]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
@@ -1850,6 +2914,65 @@ pub fn current_mode() -> SELinuxMode {
     }
 }
 
+/// Determine the current SELinux enforcing mode, assuming
+/// [`SELinuxMode::Permissive`] if SELinux is disabled or no policy is loaded.
+///
+/// This suits library code that must behave the same whether or not SELinux
+/// is active, e.g., in containers.
+///
+/// See: `security_getenforce()`.
+#[doc(alias = "security_getenforce")]
+#[must_use]
+pub fn current_mode_or_permissive() -> SELinuxMode {
+    match current_mode() {
+        SELinuxMode::NotRunning => SELinuxMode::Permissive,
+        mode => mode,
+    }
+}
+
+/// Determine the current SELinux enforcing mode, falling back to reading
+/// the `enforce` file of the SELinux file system if `security_getenforce()`
+/// fails.
+///
+/// The SELinux file system is looked up at [`selinux_mount_point`], or at
+/// `/sys/fs/selinux` if `libselinux` has no mount point.
+///
+/// This returns `None` if the current SELinux enforcing mode cannot be
+/// determined either way.
+///
+/// ⚠️ The enforcing mode can change at any time, e.g., right after the `enforce`
+/// file is read. The returned mode might therefore be stale by the time it is
+/// examined.
+///
+/// See: `security_getenforce()`.
+#[doc(alias = "security_getenforce")]
+#[must_use]
+pub fn current_mode_with_fallback() -> Option<SELinuxMode> {
+    match unsafe { selinux_sys::security_getenforce() } {
+        -1_i32 => {
+            let path = selinux_mount_point()
+                .unwrap_or_else(|| PathBuf::from(SELINUX_FS_MOUNT_POINT))
+                .join("enforce");
+            fs::read(path)
+                .ok()
+                .and_then(|bytes| parse_enforce_file(&bytes))
+        }
+
+        0_i32 => Some(SELinuxMode::Permissive),
+
+        _ => Some(SELinuxMode::Enforcing),
+    }
+}
+
+fn parse_enforce_file(bytes: &[u8]) -> Option<SELinuxMode> {
+    let value: c_int = str::from_utf8(bytes).ok()?.trim().parse().ok()?;
+    if value == 0_i32 {
+        Some(SELinuxMode::Permissive)
+    } else {
+        Some(SELinuxMode::Enforcing)
+    }
+}
+
 /// Set the current SELinux enforcing mode.
 ///
 /// See: `security_disable()`, `security_setenforce()`.
@@ -2061,6 +3184,382 @@ pub fn se_user_and_level(
     }
 }
 
+/// Return whether the policy allows `source_type` to access `target_type` via
+/// `target_class` with the requested permission.
+///
+/// The source and target contexts are formed by replacing the role and type
+/// of the current process context by `object_r` and by `source_type` or
+/// `target_type`. The `object_r` role is authorized for all types, so any
+/// defined type can be queried, whatever the role of the current process.
+/// Constraints on users, roles or ranges apply as if both contexts had the user
+/// and range of the current process, and the `object_r` role.
+///
+/// This returns an error if `target_class` or `permission` is unknown to the
+/// policy, or if the resulting contexts are invalid.
+///
+/// See: `security_compute_av_flags()`.
+#[doc(alias = "security_compute_av_flags")]
+pub fn query_allow_rule(
+    source_type: &str,
+    target_type: &str,
+    target_class: &str,
+    permission: &str,
+) -> Result<bool> {
+    let target_class = SecurityClass::from_name(target_class)?;
+    let permission = target_class.access_vector_bit(permission)?;
+
+    let current = SecurityContext::current(true)?.to_opaque()?;
+    current.set_role_str("object_r")?;
+    current.set_type_str(source_type)?;
+    let c_source_context = current.to_c_string()?;
+    current.set_type_str(target_type)?;
+    let c_target_context = current.to_c_string()?;
+
+    let source_context = SecurityContext::from_c_str(&c_source_context, true);
+    let target_context = SecurityContext::from_c_str(&c_target_context, true);
+    let decision =
+        source_context.query_access_decision(&target_context, target_class, permission)?;
+    Ok((decision.allowed & permission) == permission)
+}
+
+/// Return the domain, i.e., the type of the security context, of the process
+/// identified by `process_id`.
+///
+/// See: `getpidcon_raw()`, `context_type_get()`.
+#[doc(alias = "getpidcon_raw")]
+#[doc(alias = "context_type_get")]
+pub fn process_domain(process_id: pid_t) -> Result<String> {
+    let context = SecurityContext::of_process(process_id, true)?;
+    Ok(context.to_opaque()?.the_type()?.to_str()?.into())
+}
+
+/// Return the domain, i.e., the type of the security context, of the current
+/// process.
+///
+/// See: `getcon_raw()`, `context_type_get()`.
+#[doc(alias = "getcon_raw")]
+#[doc(alias = "context_type_get")]
+pub fn current_domain() -> Result<String> {
+    let context = SecurityContext::current(true)?;
+    Ok(context.to_opaque()?.the_type()?.to_str()?.into())
+}
+
+/// Sizes of file system objects, grouped by security context type, as computed
+/// by [`summarize_contexts_for_directory`].
+#[derive(Debug, Default)]
+pub struct TypeUsageSummary {
+    /// Total size, in bytes, of the file system objects labeled with each type.
+    pub bytes_by_type: HashMap<String, u64>,
+    /// File system objects that could not be examined, with the causes.
+    pub errors: Vec<(PathBuf, Error)>,
+}
+
+/// Sum the sizes of the file system objects under `root`, including `root`,
+/// grouped by the type of their security contexts.
+///
+/// Sizes are apparent sizes, as reported by `lstat()`, and hard links are
+/// counted once per path. Symbolic links are never followed, and objects
+/// without a security context are not counted.
+/// Objects that cannot be examined, e.g., because of denied permissions, are
+/// reported in [`TypeUsageSummary::errors`] without stopping the walk.
+///
+/// This returns an error only if `root` itself cannot be examined.
+///
+/// See: `lgetfilecon_raw()`.
+#[doc(alias = "lgetfilecon_raw")]
+pub fn summarize_contexts_for_directory(root: impl AsRef<Path>) -> Result<TypeUsageSummary> {
+    let root = root.as_ref();
+    fs::symlink_metadata(root)
+        .map_err(|err| Error::from_io_path("fs::symlink_metadata()", root, err))?;
+
+    let mut summary = TypeUsageSummary::default();
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(path) = pending.pop() {
+        match summarize_path_context(&path, &mut pending) {
+            Ok(Some((type_name, size))) => {
+                *summary.bytes_by_type.entry(type_name).or_default() += size;
+            }
+
+            Ok(None) => {}
+
+            Err(err) => summary.errors.push((path, err)),
+        }
+    }
+
+    Ok(summary)
+}
+
+fn summarize_path_context(
+    path: &Path,
+    pending: &mut Vec<PathBuf>,
+) -> Result<Option<(String, u64)>> {
+    let metadata = fs::symlink_metadata(path)
+        .map_err(|err| Error::from_io_path("fs::symlink_metadata()", path, err))?;
+
+    if metadata.is_dir() {
+        let to_error = |err| Error::from_io_path("fs::read_dir()", path, err);
+        for entry in fs::read_dir(path).map_err(to_error)? {
+            pending.push(entry.map_err(to_error)?.path());
+        }
+    }
+
+    match SecurityContext::of_path(path, false, true)? {
+        Some(context) => {
+            let type_name = context.to_opaque()?.the_type()?.to_str()?.into();
+            Ok(Some((type_name, metadata.len())))
+        }
+
+        None => Ok(None),
+    }
+}
+
+/// Counts of entries processed by [`restore_labels`].
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct RestoreReport {
+    /// Number of file system objects whose security contexts were restored.
+    pub restored: u64,
+    /// Number of entries whose file system objects no longer exist.
+    pub skipped: u64,
+    /// Number of file system objects whose security contexts could not be set.
+    pub failed: u64,
+}
+
+/// Entries written by [`backup_labels`].
+#[derive(Debug, Default)]
+pub struct BackupReport {
+    /// Number of written entries.
+    pub written: u64,
+    /// File system objects that could not be examined, with the causes.
+    pub errors: Vec<(PathBuf, Error)>,
+}
+
+/// Write the raw security contexts of the file system objects under `root`,
+/// including `root`, to `output`.
+///
+/// Each entry is made of a path relative to `root`, then a security context,
+/// each terminated by a null byte. Symbolic links are never followed, and
+/// objects without a security context are not written.
+/// Objects that cannot be examined, e.g., because they were removed during the
+/// walk, are reported in [`BackupReport::errors`] without stopping the walk.
+///
+/// This returns an error if `root` itself cannot be examined, or if `output`
+/// cannot be written.
+///
+/// See: `lgetfilecon_raw()`.
+#[doc(alias = "lgetfilecon_raw")]
+pub fn backup_labels(root: impl AsRef<Path>, output: &mut impl io::Write) -> Result<BackupReport> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let root = root.as_ref();
+    fs::symlink_metadata(root)
+        .map_err(|err| Error::from_io_path("fs::symlink_metadata()", root, err))?;
+
+    let mut report = BackupReport::default();
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(path) = pending.pop() {
+        let context = match backup_path_context(&path, &mut pending) {
+            Ok(Some(context)) => context,
+
+            Ok(None) => continue,
+
+            Err(err) => {
+                report.errors.push((path, err));
+                continue;
+            }
+        };
+
+        let relative_path = path.strip_prefix(root).unwrap_or(&path);
+        let relative_path = if relative_path.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            relative_path
+        };
+
+        let mut entry = relative_path.as_os_str().as_bytes().to_vec();
+        entry.push(0);
+        entry.extend_from_slice(context.value_bytes());
+        entry.push(0);
+
+        output
+            .write_all(&entry)
+            .map_err(|err| Error::from_io("io::Write::write_all()", err))?;
+        report.written += 1;
+    }
+
+    Ok(report)
+}
+
+fn backup_path_context(
+    path: &Path,
+    pending: &mut Vec<PathBuf>,
+) -> Result<Option<SecurityContext<'static>>> {
+    let metadata = fs::symlink_metadata(path)
+        .map_err(|err| Error::from_io_path("fs::symlink_metadata()", path, err))?;
+
+    if metadata.is_dir() {
+        let to_error = |err| Error::from_io_path("fs::read_dir()", path, err);
+        for entry in fs::read_dir(path).map_err(to_error)? {
+            pending.push(entry.map_err(to_error)?.path());
+        }
+    }
+
+    SecurityContext::of_path(path, false, true)
+}
+
+/// Read entries written by [`backup_labels`] from `input`, and set the
+/// security context of each file system object under `root`.
+///
+/// Symbolic links are never followed. Entries whose file system objects
+/// no longer exist are skipped. Failures to set a security context are
+/// counted, without stopping the restoration.
+///
+/// This returns an error if `input` cannot be read or is malformed. Entries
+/// whose paths are absolute or contain `..` components are malformed, since
+/// they could designate file system objects outside `root`.
+///
+/// See: `lsetfilecon_raw()`.
+#[doc(alias = "lsetfilecon_raw")]
+pub fn restore_labels(root: impl AsRef<Path>, input: &mut impl io::Read) -> Result<RestoreReport> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let root = root.as_ref();
+    let mut bytes = Vec::new();
+    input
+        .read_to_end(&mut bytes)
+        .map_err(|err| Error::from_io("io::Read::read_to_end()", err))?;
+
+    let malformed = || Error::from_io("restore_labels()", io::ErrorKind::InvalidData.into());
+
+    let mut report = RestoreReport::default();
+    let fields = match bytes.split_last() {
+        None => return Ok(report),
+        Some((&0, fields)) => fields,
+        Some(_) => return Err(malformed()),
+    };
+
+    let mut fields = fields.split(|&b| b == 0);
+    while let Some(relative_path) = fields.next() {
+        let context = fields.next().ok_or_else(malformed)?;
+        let relative_path = Path::new(std::ffi::OsStr::from_bytes(relative_path));
+        if !is_contained_relative_path(relative_path) {
+            return Err(malformed());
+        }
+
+        let path = root.join(relative_path);
+
+        if fs::symlink_metadata(&path).is_err() {
+            report.skipped += 1;
+            continue;
+        }
+
+        let c_context = CString::new(context).map_err(|_r| malformed())?;
+        let context = SecurityContext::from_c_str(&c_context, true);
+        if context.set_for_path(&path, false, true).is_ok() {
+            report.restored += 1;
+        } else {
+            report.failed += 1;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Return `true` if `path` is `.`, or is made only of normal components,
+/// i.e., if joining it to a directory designates an object inside it.
+fn is_contained_relative_path(path: &Path) -> bool {
+    use std::path::Component;
+
+    let mut components = path.components();
+    match components.next() {
+        Some(Component::CurDir) => components.next().is_none(),
+        Some(Component::Normal(_)) => components.all(|c| matches!(c, Component::Normal(_))),
+        _ => false,
+    }
+}
+
+/// Return the names of the capabilities that the policy grants to the domain
+/// `type_name`, from the `capability` and `capability2` security classes,
+/// in increasing bit order.
+///
+/// The check uses a context formed by replacing the role and type of the current
+/// process context by `object_r` and `type_name`, as [`query_allow_rule`] does,
+/// so domains that the current role cannot enter can be queried too.
+/// Security classes unknown to the policy are ignored.
+///
+/// See: `security_compute_av_flags()`.
+#[doc(alias = "security_compute_av_flags")]
+pub fn domain_capabilities(type_name: &str) -> Result<Vec<String>> {
+    let context = SecurityContext::current(true)?.to_opaque()?;
+    context.set_role_str("object_r")?;
+    context.set_type_str(type_name)?;
+    let c_context = context.to_c_string()?;
+    let context = SecurityContext::from_c_str(&c_context, true);
+
+    let mut capabilities = Vec::new();
+    for &class_name in &["capability", "capability2"] {
+        let class = match SecurityClass::from_name(class_name) {
+            Ok(class) => class,
+            Err(_) => continue,
+        };
+
+        let table = PermissionTable::build_for_class(class)?;
+        let decision = context.query_access_decision(&context, class, !0)?;
+        capabilities.extend(table.decode(decision.allowed).into_iter().map(String::from));
+    }
+    Ok(capabilities)
+}
+
+/// Return whether `type_name` is a type defined by the loaded policy.
+///
+/// The check is performed by validating a context formed by replacing the role
+/// and type of the current process context by `object_r` and `type_name`.
+/// The `object_r` role is authorized for all types, so the validity
+/// of the context depends only on the existence of the type.
+///
+/// This returns `None` if SELinux is not enabled.
+///
+/// See: `security_check_context()`.
+#[doc(alias = "security_check_context")]
+pub fn type_exists(type_name: &str) -> Result<Option<bool>> {
+    let context = SecurityContext::current(true)?.to_opaque()?;
+    context.set_role_str("object_r")?;
+    context.set_type_str(type_name)?;
+    let c_context = context.to_c_string()?;
+    let context = SecurityContext::from_c_str(&c_context, true);
+    Ok(context.check())
+}
+
+/// Return the names of the kernel initial security identifiers, sorted by name.
+///
+/// Each name can be given to [`SecurityContext::of_initial_kernel_context`].
+///
+/// The names are read from the SELinux file system at [`selinux_mount_point`].
+/// This returns an error if `libselinux` has no mount point.
+pub fn initial_kernel_context_names() -> Result<Vec<String>> {
+    let mount_point = selinux_mount_point().ok_or_else(|| {
+        let err = io::ErrorKind::NotFound.into();
+        Error::from_io("selinux_mount_point()", err)
+    })?;
+
+    let path = mount_point.join("initial_contexts");
+    let to_error = |err| Error::from_io_path("fs::read_dir()", path.as_path(), err);
+
+    let mut names = fs::read_dir(&path)
+        .map_err(to_error)?
+        .map(|entry| {
+            let entry = entry.map_err(to_error)?;
+            let name = entry.file_name();
+            name.into_string()
+                .map_err(|_r| Error::PathIsInvalid(entry.path()))
+        })
+        .collect::<Result<Vec<String>>>()?;
+
+    names.sort_unstable();
+    Ok(names)
+}
+
 /// Force a reset of the loaded configuration.
 ///
 /// See: `selinux_reset_config()`.
@@ -2069,6 +3568,184 @@ pub fn reset_config() {
     unsafe { selinux_sys::selinux_reset_config() }
 }
 
+extern "C" {
+    /// Mount point of the SELinux file system, as used by `libselinux`.
+    static selinux_mnt: *const c_char;
+}
+
+/// Return the mount point of the SELinux file system, as used by `libselinux`.
+///
+/// This returns `None` if `libselinux` did not find a mounted SELinux
+/// file system, and no mount point was set by [`set_selinux_mount_point`].
+///
+/// `selinux_mnt` is read without synchronization. It is changed only by
+/// [`set_selinux_mount_point`] and [`reset_selinux_mount_point`], whose callers
+/// must ensure that no other thread uses `libselinux` meanwhile.
+///
+/// See: `selinux_mnt`.
+#[doc(alias = "selinux_mnt")]
+#[must_use]
+pub fn selinux_mount_point() -> Option<PathBuf> {
+    let mount_point = unsafe { selinux_mnt };
+    if mount_point.is_null() {
+        None
+    } else {
+        Some(c_str_ptr_to_path(mount_point).to_path_buf())
+    }
+}
+
+/// Set the mount point of the SELinux file system, used by `libselinux`.
+///
+/// ⚠️ This changes global state of `libselinux`, and should be called
+/// before any other function of this crate.
+///
+/// See: `set_selinuxmnt()`.
+///
+/// # Safety
+///
+/// No other thread may use `libselinux`, directly or through this crate,
+/// while this function runs. The previous mount point is freed, and
+/// `libselinux` reads it without synchronization.
+#[doc(alias = "set_selinuxmnt")]
+pub unsafe fn set_selinux_mount_point(path: impl AsRef<Path>) -> Result<()> {
+    let c_path = os_str_to_c_string(path.as_ref().as_os_str())?;
+    selinux_sys::fini_selinuxmnt();
+    selinux_sys::set_selinuxmnt(c_path.as_ptr());
+    Ok(())
+}
+
+/// Restore the default mount point of the SELinux file system,
+/// used by `libselinux`.
+///
+/// The default locations, `/sys/fs/selinux` and then `/selinux`, are probed
+/// for a mounted SELinux file system.
+/// If none is found, then the mount point is left unset, and an error
+/// is returned.
+///
+/// ⚠️ This changes global state of `libselinux`, and should be called
+/// before any other function of this crate.
+///
+/// See: `fini_selinuxmnt()`, `set_selinuxmnt()`.
+///
+/// # Safety
+///
+/// No other thread may use `libselinux`, directly or through this crate,
+/// while this function runs. The previous mount point is freed, and
+/// `libselinux` reads it without synchronization.
+#[doc(alias = "fini_selinuxmnt")]
+pub unsafe fn reset_selinux_mount_point() -> Result<()> {
+    const SELINUX_MAGIC: u32 = 0xf97c_ff8c;
+
+    selinux_sys::fini_selinuxmnt();
+
+    for &mount_point in &[SELINUX_FS_MOUNT_POINT, OLD_SELINUX_FS_MOUNT_POINT] {
+        let c_path = str_to_c_string(mount_point)?;
+        let mut stat_fs: libc::statfs = mem::zeroed();
+        let r = libc::statfs(c_path.as_ptr(), &mut stat_fs);
+        if r == 0_i32 && stat_fs.f_type as u32 == SELINUX_MAGIC {
+            selinux_sys::set_selinuxmnt(c_path.as_ptr());
+            return Ok(());
+        }
+    }
+
+    let err = io::ErrorKind::NotFound.into();
+    Err(Error::from_io_path("statfs()", SELINUX_FS_MOUNT_POINT, err))
+}
+
+/// Name of the extended attribute holding SELinux security contexts.
+pub const SELINUX_XATTR_NAME: &CStr = c"security.selinux";
+
+/// Name of the extended attribute holding SELinux security contexts.
+pub const SELINUX_XATTR_NAME_STR: &str = "security.selinux";
+
+/// Read the SELinux security context of a file system object directly from
+/// its [`SELINUX_XATTR_NAME`] extended attribute, bypassing `libselinux`.
+///
+/// Symbolic links are followed. The returned security context is in raw format.
+///
+/// See: `getxattr()`.
+#[doc(alias = "getxattr")]
+pub fn read_selinux_xattr(path: impl AsRef<Path>) -> Result<SecurityContext<'static>> {
+    read_xattr_context_c(path.as_ref(), SELINUX_XATTR_NAME)
+}
+
+/// Read a security context of a file system object directly from its
+/// `xattr_name` extended attribute, e.g., on FUSE file systems that store
+/// labels under a custom name.
+///
+/// Symbolic links are followed. The returned security context is in raw format.
+///
+/// See: `getxattr()`.
+#[doc(alias = "getxattr")]
+pub fn read_xattr_context(
+    path: impl AsRef<Path>,
+    xattr_name: &str,
+) -> Result<SecurityContext<'static>> {
+    let c_xattr_name = str_to_c_string(xattr_name)?;
+    read_xattr_context_c(path.as_ref(), &c_xattr_name)
+}
+
+fn read_xattr_context_c(path: &Path, xattr_name: &CStr) -> Result<SecurityContext<'static>> {
+    let c_path = os_str_to_c_string(path.as_os_str())?;
+    let name = xattr_name.as_ptr();
+
+    let mut buffer: Vec<u8> = Vec::new();
+    loop {
+        let r = unsafe { libc::getxattr(c_path.as_ptr(), name, ptr::null_mut(), 0) };
+        if r == -1_isize {
+            let err = io::Error::last_os_error();
+            return Err(Error::from_io_path("getxattr()", path, err));
+        }
+
+        buffer.resize(usize::try_from(r)?, 0);
+
+        let buffer_ptr = buffer.as_mut_ptr().cast();
+        let r = unsafe { libc::getxattr(c_path.as_ptr(), name, buffer_ptr, buffer.len()) };
+        if r == -1_isize {
+            let err = io::Error::last_os_error();
+            if err.raw_os_error() != Some(libc::ERANGE) {
+                return Err(Error::from_io_path("getxattr()", path, err));
+            }
+        } else {
+            buffer.truncate(usize::try_from(r)?);
+            break;
+        }
+    }
+
+    let len = buffer.iter().position(|&b| b == 0).unwrap_or(buffer.len());
+    buffer.truncate(len);
+    let c_context = CString::new(buffer).map_err(|_r| {
+        let err = io::ErrorKind::InvalidData.into();
+        Error::from_io_path("getxattr()", path, err)
+    })?;
+    SecurityContext::from_c_str_copy(&c_context, true)
+}
+
+/// Write the SELinux security context of a file system object directly to
+/// its [`SELINUX_XATTR_NAME`] extended attribute, bypassing `libselinux`.
+///
+/// Symbolic links are followed. The security context must be in raw format.
+///
+/// See: `setxattr()`.
+#[doc(alias = "setxattr")]
+pub fn write_selinux_xattr(path: impl AsRef<Path>, context: &SecurityContext) -> Result<()> {
+    if !context.is_raw_format() {
+        return Err(Error::UnexpectedSecurityContextFormat);
+    }
+
+    let path = path.as_ref();
+    let c_path = os_str_to_c_string(path.as_os_str())?;
+    let c_context = context.to_c_string()?.ok_or_else(|| {
+        let err = io::ErrorKind::InvalidInput.into();
+        Error::from_io("SecurityContext::to_c_string()", err)
+    })?;
+    let value = c_context.to_bytes_with_nul();
+
+    let name = SELINUX_XATTR_NAME.as_ptr();
+    let r = unsafe { libc::setxattr(c_path.as_ptr(), name, value.as_ptr().cast(), value.len(), 0) };
+    ret_val_to_result_with_path("setxattr()", r, path)
+}
+
 /// Get the default type (domain) for role, and set type to refer to it.
 ///
 /// See: `get_default_type()`.
@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests;
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::errors::Result;
 use crate::utils::get_static_path;
@@ -192,6 +192,24 @@ pub fn contexts() -> Result<&'static Path> {
     get_static_path(selinux_sys::selinux_contexts_path, proc_name)
 }
 
+/// Return the path of the context configuration file named `name`, in the
+/// directory returned by [`contexts`].
+///
+/// See: `selinux_contexts_path()`.
+#[doc(alias = "selinux_contexts_path")]
+pub fn context_file(name: impl AsRef<Path>) -> Result<PathBuf> {
+    contexts().map(|path| path.join(name))
+}
+
+/// Return the path of the file defining the security context of init
+/// scripts, e.g., as used by `run_init`.
+///
+/// See: `selinux_contexts_path()`.
+#[doc(alias = "selinux_contexts_path")]
+pub fn init_scripts_context() -> Result<PathBuf> {
+    context_file("initrc_context")
+}
+
 /// Return the defines tty types for newrole securettys.
 ///
 /// See: `selinux_securetty_types_path()`.
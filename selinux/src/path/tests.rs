@@ -161,6 +161,19 @@ fn contexts() {
     assert!(path.exists() || crate::current_mode() == crate::SELinuxMode::NotRunning);
 }
 
+#[test]
+fn context_file() {
+    let path = super::context_file("initrc_context").unwrap();
+    assert!(path.starts_with(super::contexts().unwrap()));
+    assert!(path.ends_with("initrc_context"));
+}
+
+#[test]
+fn init_scripts_context() {
+    let path = super::init_scripts_context().unwrap();
+    assert_eq!(path, super::context_file("initrc_context").unwrap());
+}
+
 #[test]
 fn securetty_types() {
     let path = super::securetty_types().unwrap();
@@ -1,9 +1,11 @@
 #[cfg(test)]
 mod tests;
 
+use std::ffi::CStr;
+use std::fmt::Write;
 use std::os::raw::{c_char, c_int, c_uint};
 use std::path::Path;
-use std::{io, ptr};
+use std::{fs, io, ptr, slice};
 
 use crate::errors::{Error, Result};
 use crate::utils::*;
@@ -57,6 +59,10 @@ pub fn policy_type() -> Result<CAllocatedBlock<c_char>> {
 
 /// Get the version of the SELinux policy.
 ///
+/// This is the highest policy version supported by the running kernel,
+/// read from the `policyvers` file of the SELinux file system. It is not
+/// the version of the loaded policy, which the kernel does not report.
+///
 /// See: `security_policyvers()`.
 #[doc(alias = "security_policyvers")]
 pub fn version_number() -> Result<c_uint> {
@@ -103,3 +109,126 @@ pub fn binary_policy_path() -> Result<&'static Path> {
     let proc_name = "selinux_binary_policy_path()";
     get_static_path(selinux_sys::selinux_binary_policy_path, proc_name)
 }
+
+/// Write the names and current values of all booleans of the loaded policy
+/// to the file at `path`, as `name=true` or `name=false` lines.
+///
+/// See: `security_get_boolean_names()`, `security_get_boolean_active()`.
+#[doc(alias = "security_get_boolean_names")]
+#[doc(alias = "security_get_boolean_active")]
+pub fn dump_boolean_state(path: impl AsRef<Path>) -> Result<()> {
+    let mut contents = String::new();
+    for name in boolean_names()? {
+        let c_name = str_to_c_string(&name)?;
+        let r = unsafe { selinux_sys::security_get_boolean_active(c_name.as_ptr()) };
+        if r == -1_i32 {
+            let err = io::Error::last_os_error();
+            return Err(Error::from_io_name(
+                "security_get_boolean_active()",
+                name,
+                err,
+            ));
+        }
+
+        let _ignored = writeln!(contents, "{}={}", name, r != 0_i32);
+    }
+
+    let path = path.as_ref();
+    fs::write(path, contents).map_err(|err| Error::from_io_path("fs::write()", path, err))
+}
+
+/// Set the pending values of booleans from the file at `path`, as written by
+/// [`dump_boolean_state`], then commit them all at once.
+///
+/// Empty lines and lines starting with `#` are ignored.
+/// If some booleans cannot be set, then the others are still committed,
+/// and [`Error::PartialRestore`] is returned.
+///
+/// See: `security_set_boolean()`, `security_commit_booleans()`.
+#[doc(alias = "security_set_boolean")]
+#[doc(alias = "security_commit_booleans")]
+pub fn restore_boolean_state(path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    let contents =
+        fs::read_to_string(path).map_err(|err| Error::from_io_path("fs::read()", path, err))?;
+
+    let mut succeeded = Vec::new();
+    let mut failed = Vec::new();
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        match set_pending_boolean_from_line(line) {
+            Ok(name) => succeeded.push(name),
+            Err((name, err)) => failed.push((name, err)),
+        }
+    }
+
+    if !succeeded.is_empty() {
+        let r = unsafe { selinux_sys::security_commit_booleans() };
+        ret_val_to_result("security_commit_booleans()", r)?;
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::PartialRestore { succeeded, failed })
+    }
+}
+
+fn set_pending_boolean_from_line(line: &str) -> std::result::Result<String, (String, Error)> {
+    let (name, value) = line.split_once('=').map_or((line, None), |(name, value)| {
+        (name.trim(), Some(value.trim()))
+    });
+
+    let value: c_int = match value {
+        Some("true" | "1") => 1_i32,
+        Some("false" | "0") => 0_i32,
+        _ => {
+            let err = io::ErrorKind::InvalidData.into();
+            return Err((name.into(), Error::from_io_name("str::parse()", line, err)));
+        }
+    };
+
+    let c_name = str_to_c_string(name).map_err(|err| (String::from(name), err))?;
+    let r = unsafe { selinux_sys::security_set_boolean(c_name.as_ptr(), value) };
+    if r == -1_i32 {
+        let err = io::Error::last_os_error();
+        let err = Error::from_io_name("security_set_boolean()", name, err);
+        Err((name.into(), err))
+    } else {
+        Ok(name.into())
+    }
+}
+
+fn boolean_names() -> Result<Vec<String>> {
+    let mut names_ptr: *mut *mut c_char = ptr::null_mut();
+    let mut count: c_int = 0;
+    let r = unsafe { selinux_sys::security_get_boolean_names(&mut names_ptr, &mut count) };
+    if r == -1_i32 {
+        return Err(Error::last_io_error("security_get_boolean_names()"));
+    }
+
+    if names_ptr.is_null() {
+        return Ok(Vec::new());
+    }
+
+    let names_slice = unsafe { slice::from_raw_parts(names_ptr, usize::try_from(count)?) };
+    let names = names_slice
+        .iter()
+        .map(|&name_ptr| {
+            unsafe { CStr::from_ptr(name_ptr) }
+                .to_str()
+                .map(String::from)
+                .map_err(Into::into)
+        })
+        .collect();
+
+    for &name_ptr in names_slice {
+        unsafe { libc::free(name_ptr.cast()) };
+    }
+    unsafe { libc::free(names_ptr.cast()) };
+
+    names
+}
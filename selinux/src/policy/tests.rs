@@ -63,3 +63,25 @@ fn set_root_path() {
     let path = super::current_policy_path().unwrap();
     super::set_root_path(path).unwrap();
 }
+
+#[test]
+fn boolean_state() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("booleans");
+
+    super::dump_boolean_state(&path).unwrap();
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(contents
+        .lines()
+        .all(|line| line.ends_with("=true") || line.ends_with("=false")));
+
+    std::fs::write(&path, "# comment\n\ninvalid-line\n").unwrap();
+    let err = super::restore_boolean_state(&path).unwrap_err();
+    assert_matches::assert_matches!(
+        err,
+        crate::errors::Error::PartialRestore { ref succeeded, ref failed }
+            if succeeded.is_empty() && failed.len() == 1 && failed[0].0 == "invalid-line"
+    );
+
+    super::restore_boolean_state(dir.path().join("missing")).unwrap_err();
+}
@@ -2,7 +2,7 @@
 
 use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
 use std::io::Write;
 use std::os::raw::{c_char, c_int};
 use std::path::Path;
@@ -25,6 +25,31 @@ fn security_context_from_c_str() {
     let _ignored = format!("{:?}", &context);
 }
 
+#[test]
+fn security_context_eq_and_hash() {
+    let c_context1 = CString::new("user1:role1:type1:s0").unwrap();
+    let c_context2 = CString::new("user1:role1:type1:s0").unwrap();
+    assert_ne!(c_context1.as_ptr(), c_context2.as_ptr());
+
+    let context1 = super::SecurityContext::from_c_str(&c_context1, false);
+    let context2 = super::SecurityContext::from_c_str(&c_context2, false);
+    assert_eq!(context1, context2);
+
+    let raw_context = super::SecurityContext::from_c_str(&c_context1, true);
+    assert_ne!(context1, raw_context);
+
+    let c_context3 = CString::new("user2:role1:type1:s0").unwrap();
+    let context3 = super::SecurityContext::from_c_str(&c_context3, false);
+    assert_ne!(context1, context3);
+
+    let mut ht = HashSet::new();
+    ht.insert(context1);
+    ht.insert(context2);
+    assert_eq!(ht.len(), 1);
+    ht.insert(context3);
+    assert_eq!(ht.len(), 2);
+}
+
 #[test]
 fn security_context_from_result() {
     super::SecurityContext::from_result("xyz", 0, ptr::null_mut(), false).unwrap_err();
@@ -119,6 +144,66 @@ fn security_context_color() {
     ht.insert(scc_clone);
 }
 
+#[serial_test::serial]
+#[test]
+fn security_context_from_env() {
+    let name = "SELINUX_RS_TEST_SECURITY_CONTEXT";
+    std::env::remove_var(name);
+    assert!(super::SecurityContext::from_env(name, false)
+        .unwrap()
+        .is_none());
+
+    std::env::set_var(name, "invalid");
+    super::SecurityContext::from_env(name, false).unwrap_err();
+
+    let context = super::SecurityContext::current(false).unwrap();
+    context.set_env(name).unwrap();
+    let env_context = super::SecurityContext::from_env(name, false)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        env_context.to_c_string().unwrap(),
+        context.to_c_string().unwrap()
+    );
+
+    context.set_env("").unwrap_err();
+    context.set_env("A=B").unwrap_err();
+
+    std::env::remove_var(name);
+}
+
+#[test]
+fn security_context_url_encoded() {
+    let c_context = c"user_u:role_r:type_t:s0/x";
+    let context = super::SecurityContext::from_c_str(c_context, false);
+    assert_eq!(
+        context.to_url_encoded(),
+        "user_u%3Arole_r%3Atype_t%3As0%2Fx"
+    );
+
+    let context = super::SecurityContext::current(false).unwrap();
+    let encoded = context.to_url_encoded();
+    assert!(!encoded.contains(':'));
+    let decoded = super::SecurityContext::from_url_encoded(&encoded, false).unwrap();
+    assert_eq!(decoded, context);
+
+    for &encoded in &["invalid%", "invalid%3", "invalid%+1", "invalid%00"] {
+        super::SecurityContext::from_url_encoded(encoded, false).unwrap_err();
+    }
+}
+
+#[test]
+fn security_context_of_init_scripts() {
+    for &raw_format in &[false, true] {
+        if let Ok(context) = super::SecurityContext::of_init_scripts(raw_format) {
+            assert!(!context.as_bytes().is_empty());
+            assert_eq!(context.is_raw_format(), raw_format);
+        } else {
+            assert!(!crate::path::init_scripts_context().unwrap().exists());
+        }
+    }
+}
+
 #[test]
 fn security_context_current() {
     let mut context = super::SecurityContext::current(false).unwrap();
@@ -142,6 +227,7 @@ fn security_context_current() {
     let _securetty = context.check_securetty_context();
 
     //let _color = context.to_color().unwrap();
+    let _color = context.to_color_if_configured();
 
     context.to_translated_format().unwrap_err();
 
@@ -282,6 +368,46 @@ fn security_context_set_for_new_labeled_sockets() {
     }
 }
 
+#[test]
+fn security_context_set_default_contexts_for_new_operations() {
+    super::SecurityContext::set_default_contexts_for_new_operations().unwrap();
+}
+
+#[test]
+fn exec_contexts_guard() {
+    for &raw_format in &[false, true] {
+        let old_exec_context = super::SecurityContext::of_next_exec(raw_format).unwrap();
+        let old_fs_context =
+            super::SecurityContext::of_new_file_system_objects(raw_format).unwrap();
+
+        let context = super::SecurityContext::current(raw_format).unwrap();
+        let guard = super::ExecContextsGuard::new(&context, &context).unwrap();
+        let _ignored = format!("{:?}", &guard);
+
+        let exec_context = super::SecurityContext::of_next_exec(raw_format).unwrap();
+        assert_eq!(exec_context.unwrap().as_bytes(), context.as_bytes());
+        let fs_context = super::SecurityContext::of_new_file_system_objects(raw_format).unwrap();
+        assert_eq!(fs_context.unwrap().as_bytes(), context.as_bytes());
+
+        drop(guard);
+
+        let exec_context = super::SecurityContext::of_next_exec(raw_format).unwrap();
+        assert_eq!(
+            exec_context.as_ref().map(super::SecurityContext::as_bytes),
+            old_exec_context
+                .as_ref()
+                .map(super::SecurityContext::as_bytes)
+        );
+        let fs_context = super::SecurityContext::of_new_file_system_objects(raw_format).unwrap();
+        assert_eq!(
+            fs_context.as_ref().map(super::SecurityContext::as_bytes),
+            old_fs_context
+                .as_ref()
+                .map(super::SecurityContext::as_bytes)
+        );
+    }
+}
+
 #[test]
 fn security_context_of_initial_kernel_context() {
     for &raw_format in &[false, true] {
@@ -290,6 +416,21 @@ fn security_context_of_initial_kernel_context() {
     }
 }
 
+#[test]
+fn security_context_of_initial_kernel_contexts() {
+    let names = super::initial_kernel_context_names().unwrap();
+    for &raw_format in &[false, true] {
+        let contexts = super::SecurityContext::of_initial_kernel_contexts(raw_format).unwrap();
+        assert_eq!(contexts.len(), names.len());
+
+        for ((name, context), expected_name) in contexts.iter().zip(&names) {
+            assert_eq!(name, expected_name);
+            assert_eq!(context.is_raw_format(), raw_format);
+            assert!(!context.as_bytes().is_empty());
+        }
+    }
+}
+
 #[test]
 fn security_context_of_process() {
     let pid = process::id() as c_int;
@@ -298,6 +439,19 @@ fn security_context_of_process() {
     }
 }
 
+#[test]
+fn security_context_of_all_processes() {
+    let pid = process::id() as c_int;
+    for &raw_format in &[false, true] {
+        let contexts = super::SecurityContext::of_all_processes(raw_format).unwrap();
+        assert!(contexts.windows(2).all(|w| w[0].0 < w[1].0));
+
+        let (_, context) = contexts.iter().find(|&&(id, _)| id == pid).unwrap();
+        let expected = super::SecurityContext::of_process(pid, raw_format).unwrap();
+        assert_eq!(context.as_bytes(), expected.as_bytes());
+    }
+}
+
 #[test]
 fn security_context_of_se_user_with_selected_context() {
     //let _context =
@@ -343,6 +497,22 @@ fn security_context_default_for_se_user() {
     .unwrap();
 }
 
+#[test]
+fn security_context_fail_safe_for_se_user() {
+    match super::SecurityContext::fail_safe_for_se_user("unconfined_u", false) {
+        Ok(context) => {
+            let context = context.to_opaque().unwrap();
+            assert_eq!(context.user().unwrap().as_bytes(), b"unconfined_u");
+        }
+
+        Err(err) => {
+            assert_matches!(err, crate::errors::Error::IO1Path { .. });
+        }
+    }
+
+    super::SecurityContext::fail_safe_for_se_user("unconfined_u\0", false).unwrap_err();
+}
+
 #[test]
 fn security_context_of_media_type() {
     super::SecurityContext::of_media_type("invalid").unwrap_err();
@@ -397,6 +567,40 @@ fn security_context_of_polyinstantiation_member_decision() {
         .unwrap();
 }
 
+#[test]
+fn security_context_of_polyinstantiation_member_decisions() {
+    let context = super::SecurityContext::current(false).unwrap();
+    let raw_context = super::SecurityContext::current(true).unwrap();
+    let target_class = super::SecurityClass::from_name("process").unwrap();
+
+    let r =
+        super::SecurityContext::of_polyinstantiation_member_decisions(&[], &context, target_class);
+    assert!(r.is_empty());
+
+    let source_contexts = [
+        super::SecurityContext::current(false).unwrap(),
+        super::SecurityContext::current(true).unwrap(),
+        super::SecurityContext::current(false).unwrap(),
+    ];
+    let r = super::SecurityContext::of_polyinstantiation_member_decisions(
+        &source_contexts,
+        &context,
+        target_class,
+    );
+    assert_eq!(r.len(), source_contexts.len());
+    let _new_context = r[0].as_ref().unwrap();
+    r[1].as_ref().unwrap_err();
+    let _new_context = r[2].as_ref().unwrap();
+
+    let r = super::SecurityContext::of_polyinstantiation_member_decisions(
+        &source_contexts[1..2],
+        &raw_context,
+        target_class,
+    );
+    assert_eq!(r.len(), 1);
+    let _new_context = r[0].as_ref().unwrap();
+}
+
 #[test]
 fn security_context_validate_transition() {
     let context = super::SecurityContext::current(false).unwrap();
@@ -415,6 +619,22 @@ fn security_context_validate_transition() {
     }
 }
 
+#[test]
+fn security_context_can_transition_to_type() {
+    for &raw_format in &[false, true] {
+        let context = super::SecurityContext::current(raw_format).unwrap();
+        let c_context = context.to_c_string().unwrap().unwrap();
+        let osc = super::OpaqueSecurityContext::from_c_str(&c_context).unwrap();
+        let the_type = osc.the_type().unwrap();
+
+        let _allowed = context
+            .can_transition_to_type(the_type.to_str().unwrap())
+            .unwrap();
+
+        context.can_transition_to_type("invalid:type").unwrap_err();
+    }
+}
+
 #[test]
 fn security_context_query_access_decision() {
     let context = super::SecurityContext::current(false).unwrap();
@@ -505,6 +725,208 @@ fn security_context_set_for_path() {
     */
 }
 
+#[test]
+fn security_context_create_memory_file() {
+    for &raw_format in &[false, true] {
+        let context = super::SecurityContext::current(raw_format).unwrap();
+        context.create_memory_file("a\0b").unwrap_err();
+
+        let file = context.create_memory_file("test").unwrap();
+        let file_context = super::SecurityContext::of_file(&file, raw_format)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            file_context.to_c_string().unwrap(),
+            context.to_c_string().unwrap()
+        );
+    }
+}
+
+#[test]
+fn security_context_change_for_path() {
+    use super::ChangeContextFlags;
+
+    let context =
+        unsafe { CStr::from_ptr("unconfined_u:object_r:user_tmp_t:s0\0".as_ptr().cast()) };
+    let context = super::SecurityContext::from_c_str(context, false);
+
+    context
+        .change_for_path("/non-existent", ChangeContextFlags::empty())
+        .unwrap_err();
+    context
+        .change_for_path("/non-existent", ChangeContextFlags::TYPE)
+        .unwrap_err();
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let sub_dir = dir.path().join("sub");
+    let a = sub_dir.join("a.txt");
+    fs::create_dir(&sub_dir).unwrap();
+    fs::write(&a, "empty file").unwrap();
+
+    for &flags in &[
+        ChangeContextFlags::RECURSE,
+        ChangeContextFlags::RECURSE | ChangeContextFlags::TYPE,
+        ChangeContextFlags::TYPE | ChangeContextFlags::RANGE,
+    ] {
+        context.change_for_path(dir.path(), flags).unwrap();
+    }
+
+    let new_context = super::SecurityContext::of_path(&a, false, false)
+        .unwrap()
+        .unwrap();
+    let new_context = new_context.to_opaque().unwrap();
+    assert_eq!(new_context.the_type().unwrap().as_bytes(), b"user_tmp_t");
+
+    let outside_dir = tempfile::TempDir::new().unwrap();
+    let outside = outside_dir.path().join("outside.txt");
+    fs::write(&outside, "empty file").unwrap();
+    let old_outside_context = super::SecurityContext::of_path(&outside, false, false).unwrap();
+    std::os::unix::fs::symlink(&outside, sub_dir.join("link")).unwrap();
+
+    let flags = ChangeContextFlags::RECURSE | ChangeContextFlags::FOLLOW_SYMBOLIC_LINKS;
+    context.change_for_path(dir.path(), flags).unwrap();
+
+    let new_outside_context = super::SecurityContext::of_path(&outside, false, false).unwrap();
+    assert_eq!(new_outside_context, old_outside_context);
+}
+
+#[test]
+fn security_context_check_column_access() {
+    use super::DatabaseColumnOperation;
+
+    let context = super::SecurityContext::current(false).unwrap();
+    for &operation in &[
+        DatabaseColumnOperation::Select,
+        DatabaseColumnOperation::Insert,
+        DatabaseColumnOperation::Update,
+    ] {
+        let _allowed = context.check_column_access(&context, operation).unwrap();
+    }
+}
+
+#[test]
+fn database_column_operation() {
+    use super::DatabaseColumnOperation;
+
+    assert_eq!(DatabaseColumnOperation::Select.permission_name(), "select");
+    assert_eq!(DatabaseColumnOperation::Insert.permission_name(), "insert");
+    assert_eq!(DatabaseColumnOperation::Update.permission_name(), "update");
+    let _ignored = format!("{:?}", DatabaseColumnOperation::Select);
+}
+
+#[test]
+fn security_context_check_row_access() {
+    use super::DatabaseRowOperation;
+
+    let context = super::SecurityContext::current(false).unwrap();
+    for &operation in &[
+        DatabaseRowOperation::Select,
+        DatabaseRowOperation::Insert,
+        DatabaseRowOperation::Update,
+        DatabaseRowOperation::Delete,
+    ] {
+        let _allowed = context.check_row_access(&context, operation).unwrap();
+    }
+}
+
+#[test]
+fn database_row_operation() {
+    use super::DatabaseRowOperation;
+
+    assert_eq!(DatabaseRowOperation::Select.permission_name(), "select");
+    assert_eq!(DatabaseRowOperation::Insert.permission_name(), "insert");
+    assert_eq!(DatabaseRowOperation::Update.permission_name(), "update");
+    assert_eq!(DatabaseRowOperation::Delete.permission_name(), "delete");
+    let _ignored = format!("{:?}", DatabaseRowOperation::Select);
+}
+
+#[test]
+fn security_context_check_format() {
+    use super::{FormatCheckResult, FormatChecker, SecurityContext};
+
+    for &context in &[
+        "system_u:system_r:init_t",
+        "system_u:system_r:init_t:s0",
+        "system_u:object_r:httpd_sys_content_t:s0-s0:c0.c1023",
+        "user_u:user_r:user_t:s0:c0-s15:c0,c5",
+    ] {
+        assert_eq!(
+            SecurityContext::check_format(context),
+            FormatCheckResult::Valid
+        );
+    }
+
+    assert_eq!(
+        SecurityContext::check_format("system_u:system_r"),
+        FormatCheckResult::TooFewFields { found: 2 }
+    );
+    assert_eq!(
+        SecurityContext::check_format("a:b:c:d:e:f:g"),
+        FormatCheckResult::TooManyFields { found: 7 }
+    );
+    assert_eq!(
+        SecurityContext::check_format("system_u::init_t"),
+        FormatCheckResult::EmptyField { position: 1 }
+    );
+    assert_eq!(
+        SecurityContext::check_format("system_u:system_r\0:init_t"),
+        FormatCheckResult::NullByte { position: 17 }
+    );
+    assert_eq!(
+        SecurityContext::check_format("system_u:system r:init_t"),
+        FormatCheckResult::InvalidCharacter {
+            position: 15,
+            character: ' '
+        }
+    );
+    assert_eq!(
+        SecurityContext::check_format("system_u:system_r,x:init_t"),
+        FormatCheckResult::InvalidCharacter {
+            position: 17,
+            character: ','
+        }
+    );
+
+    let checker = FormatChecker::new().require_mls_range(true);
+    let _ignored = format!("{:?}", &checker);
+    assert_eq!(
+        checker.check("system_u:system_r:init_t"),
+        FormatCheckResult::TooFewFields { found: 3 }
+    );
+    assert_eq!(
+        checker.check("system_u:system_r:init_t:s0"),
+        FormatCheckResult::Valid
+    );
+}
+
+#[test]
+fn security_context_eq_range_insensitive() {
+    let new_context = |s: &'static str| {
+        let c_context = CStr::from_bytes_with_nul(s.as_bytes()).unwrap();
+        super::SecurityContext::from_c_str(c_context, false)
+    };
+
+    let context = new_context("user1:role1:type1:s0\0");
+    for &(other, expected) in &[
+        ("user1:role1:type1:s0\0", true),
+        ("user1:role1:type1:s0-s0:c0.c1023\0", true),
+        ("user1:role1:type1\0", true),
+        ("user2:role1:type1:s0\0", false),
+        ("user1:role2:type1:s0\0", false),
+        ("user1:role1:type2:s0\0", false),
+    ] {
+        let r = context.eq_range_insensitive(&new_context(other)).unwrap();
+        assert_eq!(r, expected);
+    }
+
+    context
+        .eq_range_insensitive(&new_context("user1\0"))
+        .unwrap_err();
+    new_context("\0")
+        .eq_range_insensitive(&context)
+        .unwrap_err();
+}
+
 #[test]
 fn security_context_of_file() {
     let mut file = tempfile::tempfile().unwrap();
@@ -553,6 +975,42 @@ fn layer_colors() {
     let _ignored = format!("{:?}", &lc);
     let mut ht = HashSet::new();
     ht.insert(lc_clone);
+
+    assert_eq!(lc.to_ansi_sequence(), "\x1b[48;2;34;0;0m\x1b[38;2;17;0;0m");
+    assert_eq!(super::LayerColors::ansi_reset_sequence(), "\x1b[0m");
+}
+
+#[test]
+fn security_context_table() {
+    use std::rc::Rc;
+
+    let mut table = super::SecurityContextTable::new();
+    assert!(table.is_empty());
+    assert!(table.get("user1:role1:type1:s0", false).is_none());
+
+    let new_context = |s: &'static str, raw_format: bool| {
+        let c_context = CStr::from_bytes_with_nul(s.as_bytes()).unwrap();
+        super::SecurityContext::from_c_str(c_context, raw_format)
+    };
+
+    let context1 = table.intern(new_context("user1:role1:type1:s0\0", false));
+    let context2 = table.intern(new_context("user1:role1:type1:s0\0", false));
+    assert!(Rc::ptr_eq(&context1, &context2));
+    assert_eq!(table.len(), 1);
+
+    let context3 = table.intern(new_context("user1:role1:type1:s0\0", true));
+    assert!(!Rc::ptr_eq(&context1, &context3));
+    let _context4 = table.intern(new_context("user2:role1:type1:s0\0", false));
+    assert_eq!(table.len(), 3);
+    assert!(!table.is_empty());
+
+    let context = table.get("user1:role1:type1:s0", false).unwrap();
+    assert!(Rc::ptr_eq(&context, &context1));
+    let context = table.get("user1:role1:type1:s0", true).unwrap();
+    assert!(Rc::ptr_eq(&context, &context3));
+    assert!(table.get("user3:role1:type1:s0", false).is_none());
+
+    let _ignored = format!("{:?}", &table);
 }
 
 #[test]
@@ -592,6 +1050,15 @@ fn security_class_new() {
     unsafe { sc.access_vector_bit_name(0) }.unwrap_err();
     let _name = unsafe { sc.access_vector_bit_name(1) }.unwrap();
 
+    assert!(sc.access_vector_bit_names(0).unwrap().is_empty());
+    let fork = sc.access_vector_bit("fork").unwrap();
+    let transition = sc.access_vector_bit("transition").unwrap();
+    let names = sc.access_vector_bit_names(fork | transition).unwrap();
+    assert_eq!(names.len(), 2);
+    assert!(names.iter().any(|name| name == "fork"));
+    assert!(names.iter().any(|name| name == "transition"));
+    sc.access_vector_bit_names(u32::MAX).unwrap_err();
+
     let _name = sc.full_access_vector_name(0).unwrap();
     let _name = sc.full_access_vector_name(1).unwrap();
     sc.full_access_vector_name(u32::MAX).unwrap_err();
@@ -601,6 +1068,23 @@ fn security_class_new() {
     let _av = sc.access_vector_bit("signal").unwrap();
 }
 
+#[test]
+fn permission_table() {
+    let sc = super::SecurityClass::from_name("process").unwrap();
+    let table = super::PermissionTable::build_for_class(sc).unwrap();
+    assert_eq!(table.class(), sc);
+    let _ignored = format!("{:?}", &table);
+
+    assert!(table.decode(0).is_empty());
+    let fork = sc.access_vector_bit("fork").unwrap();
+    let transition = sc.access_vector_bit("transition").unwrap();
+    let names = table.decode(fork | transition);
+    assert_eq!(names.len(), 2);
+    assert!(names.contains(&"fork"));
+    assert!(names.contains(&"transition"));
+    assert!(!table.decode(u32::MAX).is_empty());
+}
+
 #[test]
 fn opaque_security_context() {
     for &context in &[
@@ -706,6 +1190,45 @@ fn current_mode() {
     let _ignored = format!("{:?}", r);
 }
 
+#[test]
+fn current_mode_or_permissive() {
+    let r = super::current_mode_or_permissive();
+    assert_ne!(r, super::SELinuxMode::NotRunning);
+
+    let mode = super::current_mode();
+    if mode != super::SELinuxMode::NotRunning {
+        assert_eq!(r, mode);
+    }
+}
+
+#[test]
+fn current_mode_with_fallback() {
+    let r = super::current_mode_with_fallback();
+    assert_ne!(r, Some(super::SELinuxMode::NotRunning));
+
+    let mode = super::current_mode();
+    if mode != super::SELinuxMode::NotRunning {
+        assert_eq!(r, Some(mode));
+    }
+}
+
+#[test]
+fn parse_enforce_file() {
+    for &bytes in &[b"" as &[u8], b" ", b"x", b"-", b"1x", b"\xff"] {
+        assert!(super::parse_enforce_file(bytes).is_none());
+    }
+
+    for &bytes in &[b"0" as &[u8], b"0\n", b" 0 "] {
+        let r = super::parse_enforce_file(bytes);
+        assert_eq!(r, Some(super::SELinuxMode::Permissive));
+    }
+
+    for &bytes in &[b"1" as &[u8], b"1\n", b"2"] {
+        let r = super::parse_enforce_file(bytes);
+        assert_eq!(r, Some(super::SELinuxMode::Enforcing));
+    }
+}
+
 #[test]
 fn undefined_handling() {
     if let Err(err) = super::undefined_handling() {
@@ -815,6 +1338,31 @@ fn se_user_and_level() {
     let (_se_user, _level) = super::se_user_and_level("root", Some("file")).unwrap();
 }
 
+#[test]
+fn query_allow_rule() {
+    super::query_allow_rule("unconfined_t", "unconfined_t", "no_such_class", "read").unwrap_err();
+    super::query_allow_rule("unconfined_t", "unconfined_t", "file", "no_such_perm").unwrap_err();
+    super::query_allow_rule("no_such_t", "unconfined_t", "file", "read").unwrap_err();
+
+    assert!(super::query_allow_rule("unconfined_t", "unconfined_t", "process", "fork").unwrap());
+    assert!(super::query_allow_rule("unconfined_t", "user_tmp_t", "file", "read").unwrap());
+    assert!(!super::query_allow_rule("httpd_t", "shadow_t", "file", "read").unwrap());
+}
+
+#[test]
+fn type_exists() {
+    assert_eq!(super::type_exists("unlabeled_t").unwrap(), Some(true));
+    assert_eq!(super::type_exists("no_such_t").unwrap(), Some(false));
+}
+
+#[test]
+fn initial_kernel_context_names() {
+    let names = super::initial_kernel_context_names().unwrap();
+    assert!(names.iter().any(|name| name == "kernel"));
+    assert!(names.iter().any(|name| name == "unlabeled"));
+    assert!(names.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
 #[test]
 fn reset_config() {
     super::reset_config();
@@ -830,3 +1378,194 @@ fn set_dynamic_mapping() {
     super::set_dynamic_mapping(&[] as &[(&str, &[&str])]).unwrap();
     super::set_dynamic_mapping(&[("file", &["read", "write"] as &[&str])]).unwrap();
 }
+
+#[test]
+fn selinux_mount_point() {
+    if let Some(mount_point) = super::selinux_mount_point() {
+        assert!(mount_point.is_absolute());
+    }
+}
+
+#[test]
+fn selinux_xattr() {
+    assert_eq!(
+        super::SELINUX_XATTR_NAME.to_str().unwrap(),
+        super::SELINUX_XATTR_NAME_STR
+    );
+
+    let file = tempfile::NamedTempFile::new().unwrap();
+
+    match super::read_selinux_xattr(file.path()) {
+        Ok(context) => {
+            assert!(context.is_raw_format());
+            assert!(!context.as_bytes().contains(&0));
+
+            super::write_selinux_xattr(file.path(), &context).unwrap();
+        }
+
+        Err(err) => {
+            assert_matches!(err, crate::errors::Error::IO1Path { .. });
+        }
+    }
+
+    let context = super::SecurityContext::current(false).unwrap();
+    if !context.is_raw_format() {
+        let err = super::write_selinux_xattr(file.path(), &context).unwrap_err();
+        assert_matches!(err, crate::errors::Error::UnexpectedSecurityContextFormat);
+    }
+
+    super::read_selinux_xattr("/\0").unwrap_err();
+}
+
+#[test]
+fn read_xattr_context() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+
+    if let Ok(context) = super::read_selinux_xattr(file.path()) {
+        let other = super::read_xattr_context(file.path(), super::SELINUX_XATTR_NAME_STR).unwrap();
+        assert_eq!(context, other);
+    }
+
+    let err = super::read_xattr_context(file.path(), "user.missing").unwrap_err();
+    assert_matches!(err, crate::errors::Error::IO1Path { .. });
+
+    super::read_xattr_context(file.path(), "user.\0").unwrap_err();
+}
+
+#[test]
+fn security_context_is_mls_range_allowed() {
+    let context = super::SecurityContext::current(true).unwrap();
+    match super::kernel_support() {
+        super::KernelSupport::SELinuxMLS => {
+            let level_context = context.to_opaque().unwrap();
+            level_context.set_range_str("s0:c1").unwrap();
+            let c_level_context = level_context.to_c_string().unwrap();
+            let level_context = super::SecurityContext::from_c_str(&c_level_context, true);
+
+            // The own range of a context contains its level, but "s0:c0" does not.
+            assert!(level_context.is_mls_range_allowed("s0:c1").unwrap());
+            assert!(!level_context.is_mls_range_allowed("s0:c0").unwrap());
+
+            context.is_mls_range_allowed("s0\0").unwrap_err();
+        }
+
+        _ => {
+            let err = context.is_mls_range_allowed("s0").unwrap_err();
+            assert_matches!(err, crate::errors::Error::IO { .. });
+        }
+    }
+}
+
+#[test]
+fn security_context_with_sensitivity() {
+    let c_context = c"user_u:user_r:user_t:s0-s1:c0.c5";
+    let context = super::SecurityContext::from_c_str(c_context, true);
+
+    let new_context = context.with_default_sensitivity().unwrap();
+    assert!(new_context.is_raw_format());
+    assert_eq!(new_context.as_bytes(), b"user_u:user_r:user_t:s0");
+
+    let new_context = context.with_sensitivity("s2").unwrap();
+    assert_eq!(new_context.as_bytes(), b"user_u:user_r:user_t:s2-s2:c0.c5");
+
+    let c_context = c"user_u:user_r:user_t";
+    let context = super::SecurityContext::from_c_str(c_context, true);
+    context.with_default_sensitivity().unwrap_err();
+    context.with_sensitivity("s2").unwrap_err();
+}
+
+#[test]
+fn current_domain() {
+    let domain = super::current_domain().unwrap();
+    assert!(!domain.is_empty());
+
+    let process_id = process::id() as c_int;
+    assert_eq!(super::process_domain(process_id).unwrap(), domain);
+
+    super::process_domain(-1_i32).unwrap_err();
+}
+
+#[test]
+fn summarize_contexts_for_directory() {
+    let dir = tempfile::TempDir::new().unwrap();
+    fs::write(dir.path().join("a"), b"0123456789").unwrap();
+    fs::create_dir(dir.path().join("b")).unwrap();
+    fs::write(dir.path().join("b").join("c"), b"01234").unwrap();
+
+    let summary = super::summarize_contexts_for_directory(dir.path()).unwrap();
+    let _ignored = format!("{:?}", &summary);
+    assert!(summary.errors.is_empty());
+    let total: u64 = summary.bytes_by_type.values().sum();
+    assert!(total >= 15);
+    assert!(summary
+        .bytes_by_type
+        .keys()
+        .all(|type_name| !type_name.is_empty()));
+
+    super::summarize_contexts_for_directory(dir.path().join("missing")).unwrap_err();
+}
+
+#[test]
+fn backup_and_restore_labels() {
+    let dir = tempfile::TempDir::new().unwrap();
+    fs::write(dir.path().join("a"), b"a").unwrap();
+    fs::create_dir(dir.path().join("b")).unwrap();
+
+    let mut backup = Vec::new();
+    let report = super::backup_labels(dir.path(), &mut backup).unwrap();
+    let _ignored = format!("{:?}", &report);
+    assert_eq!(report.written, 3);
+    assert!(report.errors.is_empty());
+    assert_eq!(backup.iter().filter(|&&b| b == 0).count(), 6);
+
+    fs::remove_file(dir.path().join("a")).unwrap();
+    let report = super::restore_labels(dir.path(), &mut backup.as_slice()).unwrap();
+    let _ignored = format!("{:?}", &report);
+    assert_eq!(report.restored + report.failed, 2);
+    assert_eq!(report.skipped, 1);
+
+    let report = super::restore_labels(dir.path(), &mut &b""[..]).unwrap();
+    assert_eq!(report, super::RestoreReport::default());
+
+    super::restore_labels(dir.path(), &mut &b"a\0"[..]).unwrap_err();
+    super::restore_labels(dir.path(), &mut &b"a"[..]).unwrap_err();
+    super::restore_labels(dir.path(), &mut &b"/etc/shadow\0a\0"[..]).unwrap_err();
+    super::restore_labels(dir.path(), &mut &b"b/../../a\0a\0"[..]).unwrap_err();
+    super::restore_labels(dir.path(), &mut &b"\0a\0"[..]).unwrap_err();
+
+    super::backup_labels(dir.path().join("missing"), &mut Vec::new()).unwrap_err();
+}
+
+#[test]
+fn domain_capabilities() {
+    let domain = super::current_domain().unwrap();
+    let capabilities = super::domain_capabilities(&domain).unwrap();
+    assert!(capabilities.iter().all(|name| !name.is_empty()));
+
+    let capabilities = super::domain_capabilities("httpd_t").unwrap();
+    assert!(capabilities.iter().any(|name| name == "net_bind_service"));
+
+    super::domain_capabilities("invalid\0").unwrap_err();
+}
+
+#[test]
+fn security_context_is_domain_permissive() {
+    let context = super::SecurityContext::current(false).unwrap();
+    let _permissive = context.is_domain_permissive().unwrap();
+}
+
+#[test]
+fn security_context_check_access_respecting_permissive_mode() {
+    let context = super::SecurityContext::current(false).unwrap();
+    let result = context
+        .check_access_respecting_permissive_mode(&context, "process", "fork")
+        .unwrap();
+    let _ignored = format!("{:?}", result);
+
+    context
+        .check_access_respecting_permissive_mode(&context, "process", "invalid")
+        .unwrap_err();
+    context
+        .check_access_respecting_permissive_mode(&context, "invalid", "fork")
+        .unwrap_err();
+}
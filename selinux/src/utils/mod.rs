@@ -11,6 +11,12 @@ use once_cell::sync::OnceCell;
 
 use crate::errors::{Error, Result};
 
+/// Default mount point of the SELinux file system.
+pub(crate) const SELINUX_FS_MOUNT_POINT: &str = "/sys/fs/selinux";
+
+/// Mount point of the SELinux file system on older systems.
+pub(crate) const OLD_SELINUX_FS_MOUNT_POINT: &str = "/selinux";
+
 pub(crate) fn str_to_c_string(s: &str) -> Result<CString> {
     CString::new(s).map_err(|_r| Error::IO1Name {
         operation: "CString::new",